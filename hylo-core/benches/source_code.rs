@@ -0,0 +1,134 @@
+/*
+ * SOURCE CODE BENCHMARK
+ *
+ * Compares the indexed SourceCodeContainer with the line list it replaced,
+ * where every lookup scanned the lines from the start. Run with:
+ *
+ *     cargo bench -p hylo-core --bench source_code
+ *
+ * Each size is a generated program of that many lines. The linear store is
+ * quadratic, so it is only measured on the smaller sizes.
+ */
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use hylo_core::compiler::HyloCompiler;
+use hylo_core::lexer::source_code::SourceCodeContainer;
+
+const SIZES: [usize; 4] = [1_000, 10_000, 50_000, 100_000];
+const LINEAR_MAX_LINES: usize = 10_000;
+const RUNS: usize = 3;
+
+// The store before the line index, kept here to compare against
+struct LinearLine {
+    line: Vec<char>,
+    start_pos: usize,
+    end_pos: usize,
+}
+
+struct LinearSource {
+    lines: Vec<LinearLine>,
+    total_chars: usize,
+}
+
+impl LinearSource {
+    fn from(source_code: &str) -> Self {
+        let mut lines = Vec::new();
+        let mut pos_count = 0;
+        for l in source_code.split('\n') {
+            let mut line: Vec<char> = l.chars().collect();
+            line.push('\n');
+            let end_pos = pos_count + line.len() - 1;
+            lines.push(LinearLine { line, start_pos: pos_count, end_pos });
+            pos_count = end_pos + 1;
+        }
+        return Self { lines, total_chars: pos_count };
+    }
+
+    fn char_at(&self, pos: usize) -> Option<char> {
+        for l in self.lines.iter() {
+            if pos > l.end_pos {
+                continue;
+            }
+            return Some(l.line[pos - l.start_pos]);
+        }
+        return None;
+    }
+
+    fn get_line_and_column(&self, pos: usize) -> (usize, usize) {
+        for (idx, l) in self.lines.iter().enumerate() {
+            if pos <= l.end_pos {
+                return (idx + 1, pos - l.start_pos + 1);
+            }
+        }
+        return (self.lines.len(), 0);
+    }
+}
+
+fn generate(lines: usize) -> String {
+    let mut source = String::new();
+    for idx in 0..lines {
+        match idx % 4 {
+            0 => source.push_str(&format!("let value_{} = {} + 0x{:x} * 2;\n", idx, idx, idx)),
+            1 => source.push_str(&format!("print(\"line {} of the file\"); // a comment\n", idx)),
+            2 => source.push_str(&format!("if value_{} >= 10 {{ value_{} = value_{} - 1; }}\n", idx - 2, idx - 2, idx - 2)),
+            _ => source.push_str("/* block */ while false { break; }\n"),
+        }
+    }
+    return source;
+}
+
+// Fastest of a few runs
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    return (0..RUNS).map(|_| {
+        let start = Instant::now();
+        black_box(f());
+        start.elapsed()
+    }).min().unwrap();
+}
+
+// Every char once, the way the lexer reads them
+fn sweep_indexed(code: &SourceCodeContainer) -> usize {
+    return (0..code.total_chars).filter(|&pos| code.char_at(pos) == Some('\n')).count();
+}
+
+fn sweep_linear(code: &LinearSource) -> usize {
+    return (0..code.total_chars).filter(|&pos| code.char_at(pos) == Some('\n')).count();
+}
+
+// A line and column for every 16th char, as reporting does for spans
+fn locate_indexed(code: &SourceCodeContainer) -> usize {
+    return (0..code.total_chars).step_by(16).map(|pos| code.get_line_and_column(pos).1).sum();
+}
+
+fn locate_linear(code: &LinearSource) -> usize {
+    return (0..code.total_chars).step_by(16).map(|pos| code.get_line_and_column(pos).1).sum();
+}
+
+fn ms(duration: Duration) -> String {
+    return format!("{:.2} ms", duration.as_secs_f64() * 1000.0);
+}
+
+fn main() {
+    let compiler = HyloCompiler::new();
+    println!("{:>8} {:>10} | {:>12} {:>12} {:>12} | {:>12} {:>12}",
+        "lines", "chars", "lex", "sweep", "locate", "sweep (old)", "locate (old)");
+
+    for lines in SIZES {
+        let source = generate(lines);
+        let code = SourceCodeContainer::from(source.clone());
+
+        let lex = time(|| compiler.tokenize(&code, "bench.hy").unwrap().len());
+        let sweep = time(|| sweep_indexed(&code));
+        let locate = time(|| locate_indexed(&code));
+
+        let (sweep_old, locate_old) = if lines <= LINEAR_MAX_LINES {
+            let linear = LinearSource::from(&source);
+            (ms(time(|| sweep_linear(&linear))), ms(time(|| locate_linear(&linear))))
+        } else {
+            (String::from("-"), String::from("-"))
+        };
+
+        println!("{:>8} {:>10} | {:>12} {:>12} {:>12} | {:>12} {:>12}",
+            lines, code.total_chars, ms(lex), ms(sweep), ms(locate), sweep_old, locate_old);
+    }
+}
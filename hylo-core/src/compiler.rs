@@ -1,30 +1,54 @@
 use crate::lexer::source_code::SourceCodeContainer;
-use crate::lexer::lexer::Lexer;
+use crate::lexer::lexer::{Lexer, TokenContainer};
 
 use crate::parser::parser::Parser;
+use crate::parser::components::Program;
+use crate::lint::{LintLevels, Pragmas};
+use crate::hylo_error;
+
+// A program that compiled, along with the warnings it produced
+#[derive(Debug)]
+pub struct Compilation {
+    pub program: Program,
+    pub warnings: hylo_error::Diagnostics,
+}
 
 /* HYLO COMPILER*/
-pub struct HyloCompiler;
+#[derive(Default)]
+pub struct HyloCompiler {
+    lint_levels: LintLevels
+}
 
 impl HyloCompiler {
     pub fn new() -> Self {
-        Self {}
+        Self { lint_levels: LintLevels::new() }
+    }
+
+    pub fn with_lint_levels(mut self, lint_levels: LintLevels) -> Self {
+        self.lint_levels = lint_levels;
+        return self;
     }
 
-    pub fn compile_stdin(&self, source_code: String) {
-        let source_code = SourceCodeContainer::from(source_code);
-
-
-        let token_containers = match Lexer::parse(&source_code, "<stdin>") {
-            Ok(t) => t,
-            Err(e) => {
-                e.panic(Some(&source_code));
-            }
-        };
-        
-        
-        
-        let mut hylo_parser = Parser::new(token_containers, "<stdin>");
-        hylo_parser.parse_program();
+    // Lexes `source_code`, `file_name` is only used in diagnostics
+    pub fn tokenize(&self, source_code: &SourceCodeContainer, file_name: &str) -> Result<Vec<TokenContainer>, hylo_error::Diagnostics> {
+        let mut diagnostics = hylo_error::Diagnostics::new();
+        let token_containers = Lexer::parse(source_code, file_name, &mut diagnostics);
+        if diagnostics.has_errors() {
+            return Err(diagnostics);
+        }
+        return Ok(token_containers);
+    }
+
+    // Fails with every error and warning when there is at least one error
+    pub fn compile(&self, source_code: &SourceCodeContainer, file_name: &str) -> Result<Compilation, hylo_error::Diagnostics> {
+        let mut diagnostics = hylo_error::Diagnostics::new();
+        let (token_containers, program) = Parser::parse_source(source_code, file_name, &mut diagnostics);
+        let pragmas = Pragmas::collect(source_code, &token_containers);
+
+        let diagnostics = self.lint_levels.apply(diagnostics, &pragmas);
+        if diagnostics.has_errors() {
+            return Err(diagnostics);
+        }
+        return Ok(Compilation { program, warnings: diagnostics });
     }
-}
\ No newline at end of file
+}
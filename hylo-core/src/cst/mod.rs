@@ -0,0 +1,5 @@
+pub mod syntax_token;
+pub mod syntax_tree;
+
+pub use syntax_token::{SyntaxToken, SyntaxTokens, Trivia, TriviaKind};
+pub use syntax_tree::{NodeKind, SyntaxElement, SyntaxNode, SyntaxTree};
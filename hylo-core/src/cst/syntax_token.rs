@@ -0,0 +1,182 @@
+use crate::lexer::lexer::{Lexer, TokenContainer};
+use crate::lexer::source_code::SourceCodeContainer;
+use crate::lexer::tokens;
+use crate::hylo_error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    Whitespace,
+    // "\n" or "\r\n"
+    Newline,
+    LineComment,
+    BlockComment,
+    // Text the lexer rejected, e.g. an invalid char or an unterminated string
+    Skipped,
+}
+
+// Source text that carries no meaning for the parser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+}
+
+/*
+ * A token together with its exact source text and the trivia around it.
+ * Trailing trivia runs up to the end of the token's line, everything
+ * after that belongs to the next token's leading trivia.
+ */
+#[derive(Debug, Clone)]
+pub struct SyntaxToken {
+    pub token: tokens::Token,
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    pub leading_trivia: Vec<Trivia>,
+    pub trailing_trivia: Vec<Trivia>,
+}
+
+impl SyntaxToken {
+    // Leading trivia, text and trailing trivia, exactly as in the source
+    pub fn full_text(&self) -> String {
+        let mut text = String::new();
+        for trivia in &self.leading_trivia {
+            text.push_str(&trivia.text);
+        }
+        text.push_str(&self.text);
+        for trivia in &self.trailing_trivia {
+            text.push_str(&trivia.text);
+        }
+        return text;
+    }
+}
+
+// Every token of a file plus the trivia after the last one
+#[derive(Debug, Clone)]
+pub struct SyntaxTokens {
+    pub tokens: Vec<SyntaxToken>,
+    pub eof_trivia: Vec<Trivia>,
+}
+
+impl SyntaxTokens {
+    pub fn from(source_code: &SourceCodeContainer, token_containers: &[TokenContainer]) -> Self {
+        // The container ends every line with '\n', including the last one,
+        // so the final character is not part of the file.
+        let source_len = source_code.total_chars - 1;
+        let source_text = |start: usize, end: usize| {
+            if start >= end { String::new() } else { source_code.get_text(start, end - 1).unwrap() }
+        };
+
+        let mut syntax_tokens: Vec<SyntaxToken> = Vec::new();
+        let mut gap_start: usize = 0;
+        for t_con in token_containers {
+            let gap = split_trivia(&source_text(gap_start, t_con.start));
+            let leading_trivia = match syntax_tokens.last_mut() {
+                Some(prev_token) => attach_trailing(prev_token, gap),
+                None => gap
+            };
+
+            syntax_tokens.push(SyntaxToken {
+                token: t_con.token.clone(),
+                text: source_text(t_con.start, t_con.end + 1),
+                start: t_con.start,
+                end: t_con.end,
+                leading_trivia,
+                trailing_trivia: Vec::new(),
+            });
+            gap_start = t_con.end + 1;
+        }
+
+        let gap = split_trivia(&source_text(gap_start, source_len));
+        let eof_trivia = match syntax_tokens.last_mut() {
+            Some(prev_token) => attach_trailing(prev_token, gap),
+            None => gap
+        };
+
+        return SyntaxTokens { tokens: syntax_tokens, eof_trivia };
+    }
+
+    // Lexes `source_code` without losing any of its text
+    pub fn parse(source_code: &SourceCodeContainer, file_name: &str) -> Result<Self, Vec<hylo_error::Error>> {
+        let mut diagnostics = hylo_error::Diagnostics::new();
+        let token_containers = Lexer::parse(source_code, file_name, &mut diagnostics);
+        if diagnostics.has_errors() {
+            return Err(diagnostics.into_errors());
+        }
+        return Ok(Self::from(source_code, &token_containers));
+    }
+
+    // The source text rebuilt from the tokens
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for token in &self.tokens {
+            text.push_str(&token.full_text());
+        }
+        for trivia in &self.eof_trivia {
+            text.push_str(&trivia.text);
+        }
+        return text;
+    }
+}
+
+// Moves the trivia before the first newline onto `prev_token`, returns the rest
+fn attach_trailing(prev_token: &mut SyntaxToken, mut gap: Vec<Trivia>) -> Vec<Trivia> {
+    let split_at = gap.iter()
+        .position(|trivia| trivia.kind == TriviaKind::Newline)
+        .unwrap_or(gap.len());
+
+    let leading = gap.split_off(split_at);
+    prev_token.trailing_trivia = gap;
+    return leading;
+}
+
+// Splits the text between two tokens: whitespace, comments and whatever the lexer rejected
+fn split_trivia(text: &str) -> Vec<Trivia> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut trivia = Vec::new();
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let start = pos;
+        let kind = if chars[pos] == '\n' || (chars[pos] == '\r' && chars.get(pos+1) == Some(&'\n')) {
+            pos += if chars[pos] == '\r' { 2 } else { 1 };
+            TriviaKind::Newline
+        } else if chars[pos] == '/' && chars.get(pos+1) == Some(&'/') {
+            while pos < chars.len() && chars[pos] != '\n' && !(chars[pos] == '\r' && chars.get(pos+1) == Some(&'\n')) {
+                pos += 1;
+            }
+            TriviaKind::LineComment
+        } else if chars[pos] == '/' && chars.get(pos+1) == Some(&'*') {
+            pos += 2;
+            let mut depth = 1;
+            while pos < chars.len() && depth > 0 {
+                if chars[pos] == '/' && chars.get(pos+1) == Some(&'*') {
+                    depth += 1;
+                    pos += 2;
+                } else if chars[pos] == '*' && chars.get(pos+1) == Some(&'/') {
+                    depth -= 1;
+                    pos += 2;
+                } else { pos += 1; }
+            }
+            TriviaKind::BlockComment
+        } else if chars[pos].is_whitespace() {
+            while pos < chars.len() && chars[pos].is_whitespace() && chars[pos] != '\n'
+                && !(chars[pos] == '\r' && chars.get(pos+1) == Some(&'\n')) {
+                pos += 1;
+            }
+            TriviaKind::Whitespace
+        } else {
+            // Anything else would have been a token, the lexer reported an error for it
+            pos += 1;
+            while pos < chars.len() && !chars[pos].is_whitespace()
+                && !(chars[pos] == '/' && matches!(chars.get(pos+1), Some('/') | Some('*'))) {
+                pos += 1;
+            }
+            TriviaKind::Skipped
+        };
+
+        trivia.push(Trivia { kind, text: chars[start..pos].iter().collect() });
+    }
+
+    return trivia;
+}
@@ -0,0 +1,355 @@
+use crate::cst::syntax_token::{SyntaxToken, SyntaxTokens, Trivia};
+use crate::lexer::source_code::SourceCodeContainer;
+use crate::parser::components::{Block, ElseBody, Expr, FnDecl, Program, Span, Stmt};
+use crate::parser::parser::Parser;
+use crate::hylo_error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    Program,
+
+    // Statements
+    ExprStmt,
+    Let,
+    Assign,
+    Block,
+    If,
+    ElseBranch,
+    While,
+    Loop,
+    Break,
+    Continue,
+    FnDecl,
+    Return,
+
+    // Parts of declarations
+    Param,
+    TypeAnnotation,
+    ReturnType,
+
+    // Expressions
+    Literal,
+    Unary,
+    Binary,
+    Call,
+    Member,
+    Index,
+    Grouping,
+
+    // Tokens the parser skipped to recover from an error
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+#[derive(Debug, Clone)]
+pub struct SyntaxNode {
+    pub kind: NodeKind,
+    pub span: Span,
+    pub children: Vec<SyntaxElement>,
+}
+
+impl SyntaxNode {
+    // The exact source text of the node, including the trivia of its tokens
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => text.push_str(&node.text()),
+                SyntaxElement::Token(token) => text.push_str(&token.full_text()),
+            }
+        }
+        return text;
+    }
+
+    pub fn child_nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    pub fn child_tokens(&self) -> impl Iterator<Item = &SyntaxToken> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Token(token) => Some(token),
+            SyntaxElement::Node(_) => None,
+        })
+    }
+}
+
+/*
+ * LOSSLESS CONCRETE SYNTAX TREE
+ *
+ * Every character of the source belongs to exactly one token or to the
+ * trivia around it, so `text()` gives back the original file byte for byte.
+ */
+#[derive(Debug, Clone)]
+pub struct SyntaxTree {
+    pub root: SyntaxNode,
+    pub eof_trivia: Vec<Trivia>,
+}
+
+impl SyntaxTree {
+    /*
+     * Builds the tree of a file even when it has errors, along with them. A
+     * statement the parser could not make sense of ends up as an Error node
+     * and text the lexer rejected as skipped trivia, so the tree still holds
+     * every byte of the file.
+     */
+    pub fn parse(source_code: &SourceCodeContainer, file_name: &str) -> (Self, hylo_error::Diagnostics) {
+        let mut diagnostics = hylo_error::Diagnostics::new();
+        let (token_containers, program) = Parser::parse_source(source_code, file_name, &mut diagnostics);
+        let syntax_tokens = SyntaxTokens::from(source_code, &token_containers);
+        return (Self::from(&program, syntax_tokens), diagnostics);
+    }
+
+    // Arranges `syntax_tokens` into the shape of `program`, they must come from the same source
+    pub fn from(program: &Program, syntax_tokens: SyntaxTokens) -> Self {
+        let mut tokens = syntax_tokens.tokens.into_iter().peekable();
+        let mut root = build_node(program_shape(program), &mut tokens);
+        add_leftover(&mut root.children, NodeKind::Program, tokens.collect());
+
+        return SyntaxTree { root, eof_trivia: syntax_tokens.eof_trivia };
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = self.root.text();
+        for trivia in &self.eof_trivia {
+            text.push_str(&trivia.text);
+        }
+        return text;
+    }
+}
+
+/*
+ * The AST only keeps the spans of meaningful tokens. A Shape lists them in
+ * source order and tokens without a place in the AST, like the commas
+ * between arguments, are picked up by the node they appear in.
+ */
+struct Shape {
+    kind: NodeKind,
+    parts: Vec<Part>,
+}
+
+enum Part {
+    Token(Span),
+    Node(Shape),
+}
+
+impl Part {
+    fn start(&self) -> usize {
+        match self {
+            Part::Token(span) => span.start,
+            Part::Node(shape) => shape.parts.first().map(|part| part.start()).unwrap_or(0),
+        }
+    }
+
+    fn end(&self) -> usize {
+        match self {
+            Part::Token(span) => span.end,
+            Part::Node(shape) => shape.parts.last().map(|part| part.end()).unwrap_or(0),
+        }
+    }
+}
+
+fn build_node(shape: Shape, tokens: &mut std::iter::Peekable<impl Iterator<Item = SyntaxToken>>) -> SyntaxNode {
+    let span = match (shape.parts.first(), shape.parts.last()) {
+        (Some(first), Some(last)) => Span { start: first.start(), end: last.end() },
+        _ => Span { start: 0, end: 0 }
+    };
+
+    let mut children = Vec::new();
+    for part in shape.parts {
+        let part_start = part.start();
+        let mut leftover = Vec::new();
+        while let Some(token) = tokens.next_if(|token| token.start < part_start) {
+            leftover.push(token);
+        }
+        add_leftover(&mut children, shape.kind, leftover);
+
+        match part {
+            Part::Token(_) => {
+                if let Some(token) = tokens.next() {
+                    children.push(SyntaxElement::Token(token));
+                }
+            },
+            Part::Node(child) => children.push(SyntaxElement::Node(build_node(child, tokens))),
+        }
+    }
+
+    return SyntaxNode { kind: shape.kind, span, children };
+}
+
+/*
+ * Tokens without a place in the AST before a part of a node. Between the
+ * statements of a program or a block there is nothing else, so the tokens
+ * there were skipped after an error and are grouped into an Error node.
+ */
+fn add_leftover(children: &mut Vec<SyntaxElement>, kind: NodeKind, leftover: Vec<SyntaxToken>) {
+    let (Some(first), Some(last)) = (leftover.first(), leftover.last()) else { return; };
+    if !matches!(kind, NodeKind::Program | NodeKind::Block) {
+        children.extend(leftover.into_iter().map(SyntaxElement::Token));
+        return;
+    }
+
+    let span = Span { start: first.start, end: last.end };
+    children.push(SyntaxElement::Node(SyntaxNode {
+        kind: NodeKind::Error,
+        span,
+        children: leftover.into_iter().map(SyntaxElement::Token).collect()
+    }));
+}
+
+fn program_shape(program: &Program) -> Shape {
+    let mut parts: Vec<Part> = program.docs.iter().map(|doc| Part::Token(doc.span)).collect();
+    parts.extend(program.statements.iter().map(|stmt| Part::Node(stmt_shape(stmt))));
+    return Shape { kind: NodeKind::Program, parts };
+}
+
+fn block_shape(block: &Block) -> Shape {
+    let mut parts = vec![Part::Token(block.lbrace)];
+    parts.extend(block.statements.iter().map(|stmt| Part::Node(stmt_shape(stmt))));
+    parts.push(Part::Token(block.rbrace));
+    return Shape { kind: NodeKind::Block, parts };
+}
+
+fn fn_decl_shape(fn_decl: &FnDecl) -> Shape {
+    let mut parts: Vec<Part> = fn_decl.docs.iter().map(|doc| Part::Token(doc.span)).collect();
+    parts.push(Part::Token(fn_decl.keyword));
+    parts.push(Part::Token(fn_decl.name.span));
+    parts.push(Part::Token(fn_decl.lparen));
+    for param in &fn_decl.params {
+        parts.push(Part::Node(Shape {
+            kind: NodeKind::Param,
+            parts: vec![Part::Token(param.name.span), Part::Token(param.colon), Part::Token(param.ty.span)]
+        }));
+    }
+    parts.push(Part::Token(fn_decl.rparen));
+    if let Some(ret) = &fn_decl.ret {
+        parts.push(Part::Node(Shape {
+            kind: NodeKind::ReturnType,
+            parts: vec![Part::Token(ret.arrow), Part::Token(ret.ty.span)]
+        }));
+    }
+    parts.push(Part::Node(block_shape(&fn_decl.body)));
+
+    return Shape { kind: NodeKind::FnDecl, parts };
+}
+
+fn stmt_shape(stmt: &Stmt) -> Shape {
+    match stmt {
+        Stmt::ExprStmt { expr, semicolon } => Shape {
+            kind: NodeKind::ExprStmt,
+            parts: vec![Part::Node(expr_shape(expr)), Part::Token(*semicolon)]
+        },
+        Stmt::Let { docs, keyword, name, ty, equals, init, semicolon, .. } => {
+            let mut parts: Vec<Part> = docs.iter().map(|doc| Part::Token(doc.span)).collect();
+            parts.push(Part::Token(*keyword));
+            parts.push(Part::Token(name.span));
+            if let Some(ty) = ty {
+                parts.push(Part::Node(Shape {
+                    kind: NodeKind::TypeAnnotation,
+                    parts: vec![Part::Token(ty.colon), Part::Token(ty.ty.span)]
+                }));
+            }
+            parts.push(Part::Token(*equals));
+            parts.push(Part::Node(expr_shape(init)));
+            parts.push(Part::Token(*semicolon));
+            Shape { kind: NodeKind::Let, parts }
+        },
+        Stmt::Assign { target, equals, value, semicolon } => Shape {
+            kind: NodeKind::Assign,
+            parts: vec![
+                Part::Node(expr_shape(target)),
+                Part::Token(*equals),
+                Part::Node(expr_shape(value)),
+                Part::Token(*semicolon)
+            ]
+        },
+        Stmt::Block(block) => block_shape(block),
+        Stmt::If { keyword, condition, then_block, else_branch } => {
+            let mut parts = vec![
+                Part::Token(*keyword),
+                Part::Node(expr_shape(condition)),
+                Part::Node(block_shape(then_block))
+            ];
+            if let Some(else_branch) = else_branch {
+                let body = match &else_branch.body {
+                    ElseBody::Block(block) => block_shape(block),
+                    ElseBody::If(stmt) => stmt_shape(stmt),
+                };
+                parts.push(Part::Node(Shape {
+                    kind: NodeKind::ElseBranch,
+                    parts: vec![Part::Token(else_branch.keyword), Part::Node(body)]
+                }));
+            }
+            Shape { kind: NodeKind::If, parts }
+        },
+        Stmt::While { keyword, condition, body } => Shape {
+            kind: NodeKind::While,
+            parts: vec![Part::Token(*keyword), Part::Node(expr_shape(condition)), Part::Node(block_shape(body))]
+        },
+        Stmt::Loop { keyword, body } => Shape {
+            kind: NodeKind::Loop,
+            parts: vec![Part::Token(*keyword), Part::Node(block_shape(body))]
+        },
+        Stmt::Break { keyword, semicolon } => Shape {
+            kind: NodeKind::Break,
+            parts: vec![Part::Token(*keyword), Part::Token(*semicolon)]
+        },
+        Stmt::Continue { keyword, semicolon } => Shape {
+            kind: NodeKind::Continue,
+            parts: vec![Part::Token(*keyword), Part::Token(*semicolon)]
+        },
+        Stmt::FnDecl(fn_decl) => fn_decl_shape(fn_decl),
+        Stmt::Return { keyword, value, semicolon } => {
+            let mut parts = vec![Part::Token(*keyword)];
+            if let Some(value) = value {
+                parts.push(Part::Node(expr_shape(value)));
+            }
+            parts.push(Part::Token(*semicolon));
+            Shape { kind: NodeKind::Return, parts }
+        },
+    }
+}
+
+fn expr_shape(expr: &Expr) -> Shape {
+    match expr {
+        Expr::Literal(literal) => Shape { kind: NodeKind::Literal, parts: vec![Part::Token(literal.span())] },
+        Expr::Unary { op, expr } => Shape {
+            kind: NodeKind::Unary,
+            parts: vec![Part::Token(op.span()), Part::Node(expr_shape(expr))]
+        },
+        Expr::Binary { left, op, right } => Shape {
+            kind: NodeKind::Binary,
+            parts: vec![Part::Node(expr_shape(left)), Part::Token(op.span()), Part::Node(expr_shape(right))]
+        },
+        Expr::Call { callee, lparen, args, rparen } => {
+            let mut parts = vec![Part::Node(expr_shape(callee)), Part::Token(*lparen)];
+            parts.extend(args.iter().map(|arg| Part::Node(expr_shape(arg))));
+            parts.push(Part::Token(*rparen));
+            Shape { kind: NodeKind::Call, parts }
+        },
+        Expr::Member { obj, dot, member } => Shape {
+            kind: NodeKind::Member,
+            parts: vec![Part::Node(expr_shape(obj)), Part::Token(*dot), Part::Node(expr_shape(member))]
+        },
+        Expr::Index { obj, lbracket, index, rbracket } => Shape {
+            kind: NodeKind::Index,
+            parts: vec![
+                Part::Node(expr_shape(obj)),
+                Part::Token(*lbracket),
+                Part::Node(expr_shape(index)),
+                Part::Token(*rbracket)
+            ]
+        },
+        Expr::Grouping { lparen, expr, rparen } => Shape {
+            kind: NodeKind::Grouping,
+            parts: vec![Part::Token(*lparen), Part::Node(expr_shape(expr)), Part::Token(*rparen)]
+        },
+    }
+}
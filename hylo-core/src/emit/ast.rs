@@ -0,0 +1,197 @@
+use crate::lexer::source_code::SourceCodeContainer;
+use crate::parser::components::{Block, DeclKind, DocComment, ElseBody, Expr, FnDecl, Literal, Program, Span, Stmt};
+
+/*
+ * The syntax tree as an indented outline, one node per line followed by
+ * the line and column where it starts.
+ *
+ *   Let x @1:1
+ *     Type int @1:8
+ *     Binary + @1:14
+ *       Int 1 @1:14
+ *       Int 2 @1:18
+ */
+pub fn dump_ast(program: &Program, source_code: &SourceCodeContainer) -> String {
+    let mut dumper = AstDumper { source_code, output: String::new(), depth: 0 };
+
+    dumper.line("Program", None);
+    dumper.nested(|d| {
+        d.docs(&program.docs);
+        for stmt in &program.statements {
+            d.stmt(stmt);
+        }
+    });
+
+    return dumper.output;
+}
+
+struct AstDumper<'a> {
+    source_code: &'a SourceCodeContainer,
+    output: String,
+    depth: usize,
+}
+
+impl AstDumper<'_> {
+    fn line(&mut self, label: &str, span: Option<Span>) {
+        self.output.push_str(&"  ".repeat(self.depth));
+        self.output.push_str(label);
+        if let Some(span) = span {
+            let (line, column) = self.source_code.get_line_and_column(span.start);
+            self.output.push_str(&format!(" @{}:{}", line, column));
+        }
+        self.output.push('\n');
+    }
+
+    fn nested(&mut self, f: impl FnOnce(&mut Self)) {
+        self.depth += 1;
+        f(self);
+        self.depth -= 1;
+    }
+
+    fn docs(&mut self, docs: &[DocComment]) {
+        for doc in docs {
+            self.line(&format!("Doc {:?}", doc.text), Some(doc.span));
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        self.line("Block", Some(block.lbrace));
+        self.nested(|d| {
+            for stmt in &block.statements {
+                d.stmt(stmt);
+            }
+        });
+    }
+
+    fn fn_decl(&mut self, fn_decl: &FnDecl) {
+        self.line(&format!("FnDecl {}", fn_decl.name.name), Some(fn_decl.keyword));
+        self.nested(|d| {
+            d.docs(&fn_decl.docs);
+            for param in &fn_decl.params {
+                d.line(&format!("Param {}: {}", param.name.name, param.ty.name), Some(param.span()));
+            }
+            if let Some(ret) = &fn_decl.ret {
+                d.line(&format!("Returns {}", ret.ty.name), Some(ret.ty.span));
+            }
+            d.block(&fn_decl.body);
+        });
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::ExprStmt { expr, .. } => {
+                self.line("ExprStmt", Some(expr.span()));
+                self.nested(|d| d.expr(expr));
+            },
+            Stmt::Let { docs, kind, keyword, name, ty, init, .. } => {
+                let label = match kind {
+                    DeclKind::Let => "Let",
+                    DeclKind::Const => "Const",
+                };
+                self.line(&format!("{} {}", label, name.name), Some(*keyword));
+                self.nested(|d| {
+                    d.docs(docs);
+                    if let Some(ty) = ty {
+                        d.line(&format!("Type {}", ty.ty.name), Some(ty.ty.span));
+                    }
+                    d.expr(init);
+                });
+            },
+            Stmt::Assign { target, value, .. } => {
+                self.line("Assign", Some(target.span()));
+                self.nested(|d| {
+                    d.expr(target);
+                    d.expr(value);
+                });
+            },
+            Stmt::Block(block) => self.block(block),
+            Stmt::If { keyword, condition, then_block, else_branch } => {
+                self.line("If", Some(*keyword));
+                self.nested(|d| {
+                    d.expr(condition);
+                    d.block(then_block);
+                    if let Some(else_branch) = else_branch {
+                        d.line("Else", Some(else_branch.keyword));
+                        d.nested(|d| match &else_branch.body {
+                            ElseBody::Block(block) => d.block(block),
+                            ElseBody::If(stmt) => d.stmt(stmt),
+                        });
+                    }
+                });
+            },
+            Stmt::While { keyword, condition, body } => {
+                self.line("While", Some(*keyword));
+                self.nested(|d| {
+                    d.expr(condition);
+                    d.block(body);
+                });
+            },
+            Stmt::Loop { keyword, body } => {
+                self.line("Loop", Some(*keyword));
+                self.nested(|d| d.block(body));
+            },
+            Stmt::Break { keyword, .. } => self.line("Break", Some(*keyword)),
+            Stmt::Continue { keyword, .. } => self.line("Continue", Some(*keyword)),
+            Stmt::FnDecl(fn_decl) => self.fn_decl(fn_decl),
+            Stmt::Return { keyword, value, .. } => {
+                self.line("Return", Some(*keyword));
+                if let Some(value) = value {
+                    self.nested(|d| d.expr(value));
+                }
+            },
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Literal(literal) => {
+                let label = match literal {
+                    Literal::Int(int, _) => format!("Int {}", int.text),
+                    Literal::Float(float, _) => format!("Float {}", float.text),
+                    Literal::String(text, _) => format!("String {:?}", text),
+                    Literal::Bool(value, _) => format!("Bool {}", value),
+                    Literal::Word(name, _) => format!("Word {}", name),
+                };
+                self.line(&label, Some(literal.span()));
+            },
+            Expr::Unary { op, expr } => {
+                self.line(&format!("Unary {}", op.as_symbol()), Some(op.span()));
+                self.nested(|d| d.expr(expr));
+            },
+            Expr::Binary { left, op, right } => {
+                self.line(&format!("Binary {}", op.as_symbol()), Some(left.span()));
+                self.nested(|d| {
+                    d.expr(left);
+                    d.expr(right);
+                });
+            },
+            Expr::Call { callee, args, .. } => {
+                self.line("Call", Some(callee.span()));
+                self.nested(|d| {
+                    d.expr(callee);
+                    for arg in args {
+                        d.expr(arg);
+                    }
+                });
+            },
+            Expr::Member { obj, member, .. } => {
+                self.line("Member", Some(obj.span()));
+                self.nested(|d| {
+                    d.expr(obj);
+                    d.expr(member);
+                });
+            },
+            Expr::Index { obj, index, .. } => {
+                self.line("Index", Some(obj.span()));
+                self.nested(|d| {
+                    d.expr(obj);
+                    d.expr(index);
+                });
+            },
+            Expr::Grouping { lparen, expr, .. } => {
+                self.line("Grouping", Some(*lparen));
+                self.nested(|d| d.expr(expr));
+            },
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::json::Json;
+use crate::parser::components::{Block, DocComment, ElseBody, Expr, FnDecl, Ident, Literal, Program, Span, Stmt, Type};
+
+/*
+ * The syntax tree as JSON. Every node is an object with a "kind" and a
+ * "span" of character offsets, the end offset is inclusive.
+ */
+pub fn ast_to_json(program: &Program) -> Json {
+    return Json::object(vec![
+        ("kind", Json::from("Program")),
+        ("docs", docs(&program.docs)),
+        ("statements", Json::Array(program.statements.iter().map(stmt).collect())),
+    ]);
+}
+
+fn span(span: Span) -> Json {
+    Json::object(vec![("start", Json::from(span.start)), ("end", Json::from(span.end))])
+}
+
+fn docs(docs: &[DocComment]) -> Json {
+    Json::Array(docs.iter().map(|doc| Json::object(vec![
+        ("text", Json::from(doc.text.as_str())),
+        ("span", span(doc.span)),
+    ])).collect())
+}
+
+fn ident(ident: &Ident) -> Json {
+    Json::object(vec![("name", Json::from(ident.name.as_str())), ("span", span(ident.span))])
+}
+
+fn ty(ty: &Type) -> Json {
+    Json::object(vec![("name", Json::from(ty.name.as_str())), ("span", span(ty.span))])
+}
+
+fn block(block: &Block) -> Json {
+    Json::object(vec![
+        ("kind", Json::from("Block")),
+        ("span", span(block.span())),
+        ("statements", Json::Array(block.statements.iter().map(stmt).collect())),
+    ])
+}
+
+fn fn_decl(fn_decl: &FnDecl) -> Json {
+    let params = fn_decl.params.iter().map(|param| Json::object(vec![
+        ("name", ident(&param.name)),
+        ("type", ty(&param.ty)),
+        ("span", span(param.span())),
+    ])).collect();
+
+    Json::object(vec![
+        ("kind", Json::from("FnDecl")),
+        ("span", span(Span { start: fn_decl.keyword.start, end: fn_decl.body.rbrace.end })),
+        ("docs", docs(&fn_decl.docs)),
+        ("name", ident(&fn_decl.name)),
+        ("params", Json::Array(params)),
+        ("return_type", fn_decl.ret.as_ref().map(|ret| ty(&ret.ty)).unwrap_or(Json::Null)),
+        ("body", block(&fn_decl.body)),
+    ])
+}
+
+fn stmt(node: &Stmt) -> Json {
+    match node {
+        Stmt::ExprStmt { expr: value, .. } => Json::object(vec![
+            ("kind", Json::from("ExprStmt")),
+            ("span", span(node.span())),
+            ("expr", expr(value)),
+        ]),
+        Stmt::Let { docs: let_docs, kind, name, ty: annotation, init, .. } => Json::object(vec![
+            ("kind", Json::from("Let")),
+            ("span", span(node.span())),
+            ("docs", docs(let_docs)),
+            ("keyword", Json::from(kind.as_symbol())),
+            ("name", ident(name)),
+            ("type", annotation.as_ref().map(|annotation| ty(&annotation.ty)).unwrap_or(Json::Null)),
+            ("init", expr(init)),
+        ]),
+        Stmt::Assign { target, value, .. } => Json::object(vec![
+            ("kind", Json::from("Assign")),
+            ("span", span(node.span())),
+            ("target", expr(target)),
+            ("value", expr(value)),
+        ]),
+        Stmt::Block(body) => block(body),
+        Stmt::If { condition, then_block, else_branch, .. } => Json::object(vec![
+            ("kind", Json::from("If")),
+            ("span", span(node.span())),
+            ("condition", expr(condition)),
+            ("then", block(then_block)),
+            ("else", match else_branch.as_ref().map(|else_branch| &else_branch.body) {
+                Some(ElseBody::Block(body)) => block(body),
+                Some(ElseBody::If(else_if)) => stmt(else_if),
+                None => Json::Null,
+            }),
+        ]),
+        Stmt::While { condition, body, .. } => Json::object(vec![
+            ("kind", Json::from("While")),
+            ("span", span(node.span())),
+            ("condition", expr(condition)),
+            ("body", block(body)),
+        ]),
+        Stmt::Loop { body, .. } => Json::object(vec![
+            ("kind", Json::from("Loop")),
+            ("span", span(node.span())),
+            ("body", block(body)),
+        ]),
+        Stmt::Break { .. } => Json::object(vec![
+            ("kind", Json::from("Break")),
+            ("span", span(node.span())),
+        ]),
+        Stmt::Continue { .. } => Json::object(vec![
+            ("kind", Json::from("Continue")),
+            ("span", span(node.span())),
+        ]),
+        Stmt::FnDecl(decl) => fn_decl(decl),
+        Stmt::Return { value, .. } => Json::object(vec![
+            ("kind", Json::from("Return")),
+            ("span", span(node.span())),
+            ("value", value.as_ref().map(expr).unwrap_or(Json::Null)),
+        ]),
+    }
+}
+
+fn expr(expr_node: &Expr) -> Json {
+    let mut fields = match expr_node {
+        Expr::Literal(literal) => match literal {
+            Literal::Int(int, _) => vec![
+                ("kind", Json::from("Int")),
+                ("text", Json::from(int.text.as_str())),
+                ("value", Json::from(int.value)),
+                ("suffix", Json::from(int.suffix.map(|suffix| suffix.as_symbol()))),
+            ],
+            Literal::Float(float, _) => vec![
+                ("kind", Json::from("Float")),
+                ("text", Json::from(float.text.as_str())),
+                ("value", Json::from(float.value)),
+                ("suffix", Json::from(float.suffix.map(|suffix| suffix.as_symbol()))),
+            ],
+            Literal::String(text, _) => vec![("kind", Json::from("String")), ("value", Json::from(text.as_str()))],
+            Literal::Bool(value, _) => vec![("kind", Json::from("Bool")), ("value", Json::from(*value))],
+            Literal::Word(name, _) => vec![("kind", Json::from("Word")), ("name", Json::from(name.as_str()))],
+        },
+        Expr::Unary { op, expr: operand } => vec![
+            ("kind", Json::from("Unary")),
+            ("op", Json::from(op.as_symbol())),
+            ("expr", expr(operand)),
+        ],
+        Expr::Binary { left, op, right } => vec![
+            ("kind", Json::from("Binary")),
+            ("op", Json::from(op.as_symbol())),
+            ("left", expr(left)),
+            ("right", expr(right)),
+        ],
+        Expr::Call { callee, args, .. } => vec![
+            ("kind", Json::from("Call")),
+            ("callee", expr(callee)),
+            ("args", Json::Array(args.iter().map(expr).collect())),
+        ],
+        Expr::Member { obj, member, .. } => vec![
+            ("kind", Json::from("Member")),
+            ("object", expr(obj)),
+            ("member", expr(member)),
+        ],
+        Expr::Index { obj, index, .. } => vec![
+            ("kind", Json::from("Index")),
+            ("object", expr(obj)),
+            ("index", expr(index)),
+        ],
+        Expr::Grouping { expr: inner, .. } => vec![("kind", Json::from("Grouping")), ("expr", expr(inner))],
+    };
+
+    fields.insert(1, ("span", span(expr_node.span())));
+    return Json::object(fields);
+}
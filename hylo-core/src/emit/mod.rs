@@ -0,0 +1,45 @@
+pub mod tokens;
+pub mod ast;
+pub mod ast_json;
+
+pub use tokens::dump_tokens;
+pub use ast::dump_ast;
+pub use ast_json::ast_to_json;
+
+// The stages of the pipeline that `--emit` can print
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmitKind {
+    Tokens,
+    Ast,
+    AstJson,
+    Ir,
+    Bytecode,
+}
+
+impl EmitKind {
+    pub fn from(name: &str) -> Option<Self> {
+        match name {
+            "tokens" => Some(EmitKind::Tokens),
+            "ast" => Some(EmitKind::Ast),
+            "ast-json" => Some(EmitKind::AstJson),
+            "ir" => Some(EmitKind::Ir),
+            "bytecode" => Some(EmitKind::Bytecode),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmitKind::Tokens => "tokens",
+            EmitKind::Ast => "ast",
+            EmitKind::AstJson => "ast-json",
+            EmitKind::Ir => "ir",
+            EmitKind::Bytecode => "bytecode",
+        }
+    }
+
+    // Stages the compiler does not produce yet
+    pub fn is_available(&self) -> bool {
+        !matches!(self, EmitKind::Ir | EmitKind::Bytecode)
+    }
+}
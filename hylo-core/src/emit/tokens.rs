@@ -0,0 +1,47 @@
+use crate::lexer::lexer::TokenContainer;
+use crate::lexer::source_code::SourceCodeContainer;
+use crate::lexer::tokens::Token;
+
+/*
+ * One token per line: where it starts, what kind it is and its source
+ * text. Number literals also show the value the lexer read.
+ *
+ *   1:1     keyword   let
+ *   1:9     int       0xFF_u8 = 255 u8
+ */
+pub fn dump_tokens(token_containers: &[TokenContainer], source_code: &SourceCodeContainer) -> String {
+    let mut output = String::new();
+    for t_con in token_containers {
+        let (line, column) = source_code.get_line_and_column(t_con.start);
+        let text = source_code.get_text(t_con.start, t_con.end).unwrap_or_default();
+
+        let detail = match &t_con.token {
+            Token::Int(int) => match int.suffix {
+                Some(suffix) => format!("{} = {} {}", text, int.value, suffix.as_symbol()),
+                None => format!("{} = {}", text, int.value),
+            },
+            Token::Float(float) => match float.suffix {
+                Some(suffix) => format!("{} = {:?} {}", text, float.value, suffix.as_symbol()),
+                None => format!("{} = {:?}", text, float.value),
+            },
+            _ => text,
+        };
+
+        output.push_str(&format!("{:<8}{:<10}{}\n", format!("{}:{}", line, column), token_kind(&t_con.token), detail));
+    }
+    return output;
+}
+
+pub fn token_kind(token: &Token) -> &'static str {
+    match token {
+        Token::Int(_) => "int",
+        Token::Float(_) => "float",
+        Token::String(_) => "string",
+        Token::Boolean(_) => "bool",
+        Token::Word(_) => "word",
+        Token::DocComment(..) => "doc",
+        Token::Keyword(_) => "keyword",
+        Token::Operator(_) => "operator",
+        Token::Punctuation(_) => "punct",
+    }
+}
@@ -0,0 +1,151 @@
+/*
+ * LAYOUT DOCUMENTS
+ *
+ * The formatter describes the output as a Doc and the printer decides
+ * where to break lines. A Group is printed on one line when it fits in
+ * the remaining width, otherwise every Line directly inside it breaks.
+ */
+#[derive(Debug, Clone)]
+pub enum Doc {
+    Text(String),
+    // A space, or a newline when the group breaks
+    Line,
+    // Nothing, or a newline when the group breaks
+    SoftLine,
+    // Always a newline, consecutive ones collapse into one
+    HardLine,
+    // An empty line before the next text, collapses like HardLine
+    BlankLine,
+    Indent(Box<Doc>),
+    Group(Box<Doc>),
+    Concat(Vec<Doc>),
+}
+
+impl Doc {
+    pub fn text(text: &str) -> Doc {
+        Doc::Text(String::from(text))
+    }
+
+    pub fn indent(doc: Doc) -> Doc {
+        Doc::Indent(Box::new(doc))
+    }
+
+    pub fn group(doc: Doc) -> Doc {
+        Doc::Group(Box::new(doc))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+struct Printer {
+    output: String,
+    max_width: usize,
+    indent_width: usize,
+    column: usize,
+    // Newlines at the end of `output`
+    pending_newlines: usize,
+}
+
+impl Printer {
+    fn write_text(&mut self, text: &str, indent: usize) {
+        // Spaces never start a line
+        let text = if self.column == 0 { text.trim_start_matches(' ') } else { text };
+        if text.is_empty() { return; }
+
+        if self.column == 0 && !self.output.is_empty() {
+            let indentation = " ".repeat(indent * self.indent_width);
+            self.output.push_str(&indentation);
+            self.column = indentation.len();
+        }
+        self.output.push_str(text);
+        self.column += text.chars().count();
+        self.pending_newlines = 0;
+    }
+
+    fn newline(&mut self, count: usize) {
+        if self.output.is_empty() { return; }
+
+        // Nor end one
+        let trimmed_len = self.output.trim_end_matches(' ').len();
+        self.output.truncate(trimmed_len);
+        while self.pending_newlines < count {
+            self.output.push('\n');
+            self.pending_newlines += 1;
+        }
+        self.column = 0;
+    }
+
+    // Whether `doc` printed flat, followed by `rest`, fits up to the next line break
+    fn fits(&self, doc: &Doc, rest: &[(usize, Mode, &Doc)]) -> bool {
+        let mut remaining = self.max_width as isize - self.column as isize;
+        let mut stack: Vec<(Mode, &Doc)> = vec![(Mode::Flat, doc)];
+        let mut rest_idx = rest.len();
+
+        loop {
+            let (mode, doc) = match stack.pop() {
+                Some(item) => item,
+                None => {
+                    if rest_idx == 0 { return true; }
+                    rest_idx -= 1;
+                    (rest[rest_idx].1, rest[rest_idx].2)
+                }
+            };
+
+            match doc {
+                Doc::Text(text) => {
+                    remaining -= text.chars().count() as isize;
+                    if remaining < 0 { return false; }
+                },
+                Doc::Line | Doc::SoftLine => {
+                    if mode == Mode::Break { return true; }
+                    if matches!(doc, Doc::Line) { remaining -= 1; }
+                    if remaining < 0 { return false; }
+                },
+                Doc::HardLine | Doc::BlankLine => return mode == Mode::Break,
+                Doc::Indent(inner) | Doc::Group(inner) => stack.push((mode, inner)),
+                Doc::Concat(docs) => {
+                    for inner in docs.iter().rev() {
+                        stack.push((mode, inner));
+                    }
+                },
+            }
+        }
+    }
+}
+
+// Lays `doc` out within `max_width` columns
+pub fn print(doc: &Doc, max_width: usize, indent_width: usize) -> String {
+    let mut printer = Printer { output: String::new(), max_width, indent_width, column: 0, pending_newlines: 0 };
+    let mut stack: Vec<(usize, Mode, &Doc)> = vec![(0, Mode::Break, doc)];
+
+    while let Some((indent, mode, doc)) = stack.pop() {
+        match doc {
+            Doc::Text(text) => printer.write_text(text, indent),
+            Doc::Line => match mode {
+                Mode::Flat => printer.write_text(" ", indent),
+                Mode::Break => printer.newline(1),
+            },
+            Doc::SoftLine => {
+                if mode == Mode::Break { printer.newline(1); }
+            },
+            Doc::HardLine => printer.newline(1),
+            Doc::BlankLine => printer.newline(2),
+            Doc::Indent(inner) => stack.push((indent + 1, mode, inner)),
+            Doc::Group(inner) => {
+                let group_mode = if mode == Mode::Flat || printer.fits(inner, &stack) { Mode::Flat } else { Mode::Break };
+                stack.push((indent, group_mode, inner));
+            },
+            Doc::Concat(docs) => {
+                for inner in docs.iter().rev() {
+                    stack.push((indent, mode, inner));
+                }
+            },
+        }
+    }
+
+    return String::from(printer.output.trim_end());
+}
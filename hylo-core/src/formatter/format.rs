@@ -0,0 +1,403 @@
+use crate::cst::{NodeKind, SyntaxElement, SyntaxNode, SyntaxToken, SyntaxTree, Trivia, TriviaKind};
+use crate::formatter::doc::Doc;
+use crate::lexer::tokens;
+
+/*
+ * SYNTAX TREE TO LAYOUT
+ *
+ * Tokens are printed from the concrete syntax tree so that every comment
+ * in the trivia finds its way into the output. Only the spacing between
+ * tokens and the line breaks are decided here.
+ */
+pub fn format_tree(tree: &SyntaxTree) -> Doc {
+    let mut docs = vec![statements(&tree.root.children)];
+
+    let mut newlines = 0;
+    for trivia in &tree.eof_trivia {
+        match trivia.kind {
+            TriviaKind::Newline => newlines += 1,
+            TriviaKind::Whitespace | TriviaKind::Skipped => {},
+            TriviaKind::LineComment | TriviaKind::BlockComment => {
+                docs.push(separator(newlines));
+                docs.push(Doc::text(&trivia.text));
+                newlines = 0;
+            },
+        }
+    }
+
+    return Doc::Concat(docs);
+}
+
+// Statements one per line, keeping at most one empty line between them
+fn statements(elements: &[SyntaxElement]) -> Doc {
+    let mut docs = Vec::new();
+    for (idx, element) in elements.iter().enumerate() {
+        if idx > 0 {
+            let blank = newlines_before_comment(&first_token(element).leading_trivia) >= 2;
+            docs.push(if blank { Doc::BlankLine } else { Doc::HardLine });
+        }
+        docs.push(element_doc(element));
+    }
+    return Doc::Concat(docs);
+}
+
+fn element_doc(element: &SyntaxElement) -> Doc {
+    match element {
+        SyntaxElement::Node(node) => node_doc(node),
+        SyntaxElement::Token(token) => token_doc(token),
+    }
+}
+
+fn node_doc(node: &SyntaxNode) -> Doc {
+    match node.kind {
+        NodeKind::Program => statements(&node.children),
+        NodeKind::Block => block(node),
+        NodeKind::Let => {
+            let (mut docs, rest) = doc_comments(&node.children);
+            docs.push(spaced(rest));
+            Doc::Concat(docs)
+        },
+        NodeKind::FnDecl => fn_decl(node),
+        NodeKind::ExprStmt | NodeKind::Assign | NodeKind::If | NodeKind::ElseBranch | NodeKind::While
+            | NodeKind::Loop | NodeKind::Break | NodeKind::Continue | NodeKind::Return
+            | NodeKind::TypeAnnotation | NodeKind::ReturnType | NodeKind::Error => spaced(&node.children),
+        NodeKind::Param => {
+            let mut docs = Vec::new();
+            for element in &node.children {
+                if is_token(element, ":") {
+                    docs.push(element_doc(element));
+                    docs.push(Doc::text(" "));
+                } else { docs.push(element_doc(element)); }
+            }
+            Doc::Concat(docs)
+        },
+        NodeKind::Unary => {
+            let mut docs = Vec::new();
+            for (idx, element) in node.children.iter().enumerate() {
+                // `- -a` must not become `--a`
+                if idx > 0 && matches!(first_token(element).token, tokens::Token::Operator(_)) {
+                    docs.push(Doc::text(" "));
+                }
+                docs.push(element_doc(element));
+            }
+            Doc::Concat(docs)
+        },
+        NodeKind::Binary => binary(node),
+        NodeKind::Call => {
+            let open = node.children.iter()
+                .position(|element| is_token(element, "("))
+                .unwrap_or(node.children.len());
+            let mut docs: Vec<Doc> = node.children[..open].iter().map(element_doc).collect();
+            docs.push(delimited(&node.children[open..]));
+            Doc::Concat(docs)
+        },
+        NodeKind::Literal | NodeKind::Member | NodeKind::Index | NodeKind::Grouping => {
+            Doc::Concat(node.children.iter().map(element_doc).collect())
+        },
+    }
+}
+
+// Elements separated by single spaces, except before `;` and type annotations
+fn spaced(elements: &[SyntaxElement]) -> Doc {
+    let mut docs = Vec::new();
+    for (idx, element) in elements.iter().enumerate() {
+        let tight = is_token(element, ";")
+            || matches!(element, SyntaxElement::Node(node) if node.kind == NodeKind::TypeAnnotation);
+        if idx > 0 && !tight {
+            docs.push(Doc::text(" "));
+        }
+        docs.push(element_doc(element));
+    }
+    return Doc::Concat(docs);
+}
+
+// Splits off the leading doc comments, each printed on its own line
+fn doc_comments(elements: &[SyntaxElement]) -> (Vec<Doc>, &[SyntaxElement]) {
+    let count = elements.iter()
+        .take_while(|element| matches!(element, SyntaxElement::Token(token) if matches!(token.token, tokens::Token::DocComment(..))))
+        .count();
+
+    let mut docs = Vec::new();
+    for element in &elements[..count] {
+        docs.push(element_doc(element));
+        docs.push(Doc::HardLine);
+    }
+    return (docs, &elements[count..]);
+}
+
+fn block(node: &SyntaxNode) -> Doc {
+    let (lbrace, inner) = match node.children.split_first() {
+        Some(split) => split,
+        None => return Doc::Concat(Vec::new())
+    };
+    let (rbrace, statements_part) = match inner.split_last() {
+        Some((SyntaxElement::Token(rbrace), rest)) if rbrace.text == "}" => (rbrace, rest),
+        _ => return Doc::Concat(node.children.iter().map(element_doc).collect())
+    };
+
+    let rbrace_comments = leading_comments(&rbrace.leading_trivia);
+    if statements_part.is_empty() && rbrace_comments.is_none() && !has_comments(&first_token(lbrace).trailing_trivia) {
+        return Doc::Concat(vec![element_doc(lbrace), Doc::text("}")]);
+    }
+
+    let mut body = vec![Doc::HardLine, statements(statements_part)];
+    if let Some(comments) = rbrace_comments {
+        body.push(comments);
+    }
+
+    return Doc::Concat(vec![
+        element_doc(lbrace),
+        Doc::indent(Doc::Concat(body)),
+        Doc::HardLine,
+        Doc::text("}"),
+        trailing_comments(&rbrace.trailing_trivia),
+    ]);
+}
+
+fn fn_decl(node: &SyntaxNode) -> Doc {
+    let (mut docs, rest) = doc_comments(&node.children);
+
+    let open = rest.iter().position(|element| is_token(element, "(")).unwrap_or(rest.len());
+    let close = rest.iter().position(|element| is_token(element, ")")).map(|idx| idx + 1).unwrap_or(rest.len());
+
+    // `fn name`
+    docs.push(spaced(&rest[..open]));
+    docs.push(delimited(&rest[open..close]));
+    for element in &rest[close..] {
+        docs.push(Doc::text(" "));
+        docs.push(element_doc(element));
+    }
+
+    return Doc::Concat(docs);
+}
+
+/*
+ * A parenthesized, comma separated list. It stays on one line when it
+ * fits, otherwise every item goes on its own indented line.
+ */
+fn delimited(elements: &[SyntaxElement]) -> Doc {
+    let (open, rest) = match elements.split_first() {
+        Some(split) => split,
+        None => return Doc::Concat(Vec::new())
+    };
+    let (close, items) = match rest.split_last() {
+        Some(split) => split,
+        None => return element_doc(open)
+    };
+
+    if items.is_empty() {
+        return Doc::Concat(vec![open_doc(open), element_doc(close)]);
+    }
+
+    let mut inner = Vec::new();
+    // Keeps `(/* first */ a` apart when the list stays on one line
+    if let SyntaxElement::Token(token) = open {
+        let last_comment = token.trailing_trivia.iter().rev().find(|piece| is_comment(piece));
+        if last_comment.is_some_and(|piece| piece.kind == TriviaKind::BlockComment) {
+            inner.push(Doc::text(" "));
+        }
+    }
+    inner.push(Doc::SoftLine);
+    for element in items {
+        inner.push(element_doc(element));
+        if is_token(element, ",") {
+            inner.push(Doc::Line);
+        }
+    }
+
+    return Doc::group(Doc::Concat(vec![
+        open_doc(open),
+        Doc::indent(Doc::Concat(inner)),
+        Doc::SoftLine,
+        element_doc(close),
+    ]));
+}
+
+/*
+ * Chains of operators with the same precedence, like `a + b - c`, form a
+ * single group so that a long chain breaks after each of its operators.
+ */
+fn binary(node: &SyntaxNode) -> Doc {
+    let mut parts = Vec::new();
+    flatten_binary(node, precedence(node), &mut parts);
+
+    let (first, rest) = match parts.split_first() {
+        Some(split) => split,
+        None => return Doc::Concat(Vec::new())
+    };
+
+    // Operators and operands alternate after the first operand
+    let mut tail = Vec::new();
+    let mut prev = *first;
+    for pair in rest.chunks(2) {
+        // A line comment already ended the line, the operator starts the next one
+        if ends_with_line_comment(prev) {
+            tail.push(element_doc(pair[0]));
+            tail.push(Doc::text(" "));
+        } else {
+            tail.push(Doc::text(" "));
+            tail.push(element_doc(pair[0]));
+            tail.push(if ends_with_line_comment(pair[0]) { Doc::Concat(Vec::new()) } else { Doc::Line });
+        }
+        if let Some(operand) = pair.get(1) {
+            tail.push(element_doc(operand));
+            prev = operand;
+        }
+    }
+
+    return Doc::group(Doc::Concat(vec![element_doc(first), Doc::indent(Doc::Concat(tail))]));
+}
+
+fn flatten_binary<'a>(node: &'a SyntaxNode, level: Option<u8>, parts: &mut Vec<&'a SyntaxElement>) {
+    for (idx, element) in node.children.iter().enumerate() {
+        match element {
+            SyntaxElement::Node(child) if idx == 0 && child.kind == NodeKind::Binary && precedence(child) == level => {
+                flatten_binary(child, level, parts);
+            },
+            _ => parts.push(element),
+        }
+    }
+}
+
+fn precedence(node: &SyntaxNode) -> Option<u8> {
+    let op = node.child_tokens().next()?;
+    let level = match op.text.as_str() {
+        "||" => 1,
+        "&&" => 2,
+        "==" | "!=" => 3,
+        "<" | ">" | "<=" | ">=" => 4,
+        "+" | "-" => 5,
+        _ => 6
+    };
+    return Some(level);
+}
+
+// A token with the comments around it
+fn token_doc(token: &SyntaxToken) -> Doc {
+    let mut docs = Vec::new();
+    if let Some(comments) = leading_comments(&token.leading_trivia) {
+        docs.push(comments);
+        docs.push(separator(newlines_after_comment(&token.leading_trivia)));
+    }
+    docs.push(Doc::text(&token.text));
+    docs.push(trailing_comments(&token.trailing_trivia));
+    return Doc::Concat(docs);
+}
+
+// An opening bracket keeps the block comments after it against it, `f(/* none */)`
+fn open_doc(element: &SyntaxElement) -> Doc {
+    let token = match element {
+        SyntaxElement::Token(token) => token,
+        SyntaxElement::Node(_) => return element_doc(element),
+    };
+
+    let mut docs = Vec::new();
+    if let Some(comments) = leading_comments(&token.leading_trivia) {
+        docs.push(comments);
+        docs.push(separator(newlines_after_comment(&token.leading_trivia)));
+    }
+    docs.push(Doc::text(&token.text));
+    for (idx, piece) in token.trailing_trivia.iter().filter(|piece| is_comment(piece)).enumerate() {
+        let space = if idx == 0 && piece.kind == TriviaKind::BlockComment { "" } else { " " };
+        docs.push(Doc::Text(format!("{}{}", space, piece.text)));
+        if piece.kind == TriviaKind::LineComment {
+            docs.push(Doc::HardLine);
+        }
+    }
+    return Doc::Concat(docs);
+}
+
+// The comments of leading trivia, each on its own line unless they shared one
+fn leading_comments(trivia: &[Trivia]) -> Option<Doc> {
+    let mut docs = Vec::new();
+    let mut newlines = 0;
+    let mut first = true;
+
+    for piece in trivia {
+        match piece.kind {
+            TriviaKind::Newline => newlines += 1,
+            TriviaKind::Whitespace | TriviaKind::Skipped => {},
+            TriviaKind::LineComment | TriviaKind::BlockComment => {
+                // Empty lines before the first comment are up to the caller
+                docs.push(if first { separator(newlines.min(1)) } else { separator(newlines) });
+                docs.push(Doc::text(&piece.text));
+                newlines = 0;
+                first = false;
+            },
+        }
+    }
+
+    if first { return None; }
+    return Some(Doc::Concat(docs));
+}
+
+// Comments after a token on its line, a line comment ends the line
+fn trailing_comments(trivia: &[Trivia]) -> Doc {
+    let mut docs = Vec::new();
+    for piece in trivia {
+        match piece.kind {
+            TriviaKind::LineComment => {
+                docs.push(Doc::Text(format!(" {}", piece.text)));
+                docs.push(Doc::HardLine);
+            },
+            TriviaKind::BlockComment => docs.push(Doc::Text(format!(" {}", piece.text))),
+            TriviaKind::Newline | TriviaKind::Whitespace | TriviaKind::Skipped => {},
+        }
+    }
+    return Doc::Concat(docs);
+}
+
+// What goes between two pieces that had `newlines` line breaks between them
+fn separator(newlines: usize) -> Doc {
+    match newlines {
+        0 => Doc::text(" "),
+        1 => Doc::HardLine,
+        _ => Doc::BlankLine,
+    }
+}
+
+fn is_comment(piece: &Trivia) -> bool {
+    matches!(piece.kind, TriviaKind::LineComment | TriviaKind::BlockComment)
+}
+
+fn has_comments(trivia: &[Trivia]) -> bool {
+    trivia.iter().any(is_comment)
+}
+
+// Whether the element's last token has a line comment after it
+fn ends_with_line_comment(element: &SyntaxElement) -> bool {
+    return last_token(element).trailing_trivia.iter().any(|piece| piece.kind == TriviaKind::LineComment);
+}
+
+fn newlines_before_comment(trivia: &[Trivia]) -> usize {
+    trivia.iter()
+        .take_while(|piece| !matches!(piece.kind, TriviaKind::LineComment | TriviaKind::BlockComment))
+        .filter(|piece| piece.kind == TriviaKind::Newline)
+        .count()
+}
+
+fn newlines_after_comment(trivia: &[Trivia]) -> usize {
+    trivia.iter()
+        .rev()
+        .take_while(|piece| !matches!(piece.kind, TriviaKind::LineComment | TriviaKind::BlockComment))
+        .filter(|piece| piece.kind == TriviaKind::Newline)
+        .count()
+}
+
+fn first_token(element: &SyntaxElement) -> &SyntaxToken {
+    match element {
+        SyntaxElement::Token(token) => token,
+        SyntaxElement::Node(node) => first_token(&node.children[0]),
+    }
+}
+
+fn last_token(element: &SyntaxElement) -> &SyntaxToken {
+    match element {
+        SyntaxElement::Token(token) => token,
+        SyntaxElement::Node(node) => last_token(node.children.last().unwrap()),
+    }
+}
+
+fn is_token(element: &SyntaxElement, text: &str) -> bool {
+    matches!(element, SyntaxElement::Token(token) if token.text == text)
+}
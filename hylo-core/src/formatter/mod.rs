@@ -0,0 +1,40 @@
+pub mod doc;
+pub mod format;
+
+use crate::cst::SyntaxTree;
+use crate::lexer::source_code::SourceCodeContainer;
+use crate::hylo_error;
+
+pub struct FormatOptions {
+    // Lines longer than this are broken where the code allows it
+    pub max_width: usize,
+    pub indent_width: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self { max_width: 100, indent_width: 4 }
+    }
+}
+
+/*
+ * HYLO FORMATTER
+ *
+ * Prints a file in the canonical style, keeping all of its comments.
+ * Formatting already formatted code gives it back unchanged. Files with
+ * errors are left alone, so there are no Error nodes or skipped text to
+ * print.
+ */
+pub fn format_source(source_code: &SourceCodeContainer, file_name: &str, options: &FormatOptions) -> Result<String, Vec<hylo_error::Error>> {
+    let (tree, diagnostics) = SyntaxTree::parse(source_code, file_name);
+    if diagnostics.has_errors() {
+        return Err(diagnostics.into_errors());
+    }
+    let doc = format::format_tree(&tree);
+
+    let mut output = doc::print(&doc, options.max_width, options.indent_width);
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    return Ok(output);
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+use crate::lexer::source_code::SourceCodeContainer;
+use super::report::Error;
+use super::severity::Severity;
+use super::theme::{Style, Theme};
+
+/*
+ * DIAGNOSTICS
+ *
+ * Collects the errors of every stage of the compiler so that a run can
+ * report all of them at once instead of stopping at the first one.
+ * Warnings are collected alongside, only errors make a run fail.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diagnostics {
+    errors: Vec<Error>
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    pub fn push(&mut self, error: Error) {
+        self.errors.push(error);
+    }
+
+    pub fn extend(&mut self, errors: impl IntoIterator<Item = Error>) {
+        self.errors.extend(errors);
+    }
+
+    // Orders the diagnostics by where they start, keeping the order of ties
+    pub fn sort(&mut self) {
+        self.errors.sort_by_key(|e| e.span.start);
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.errors.iter().filter(|e| e.severity == Severity::Error).count()
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.errors.iter().filter(|e| e.severity == Severity::Warning).count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Error> {
+        self.errors.iter()
+    }
+
+    pub fn into_errors(self) -> Vec<Error> {
+        self.errors
+    }
+
+    // Exit code for a run that produced these errors, 0 when there are none
+    pub fn exit_code(&self) -> i32 {
+        self.errors.iter()
+            .find(|e| e.severity == Severity::Error)
+            .map(|e| e.kind.exit_code())
+            .unwrap_or(0)
+    }
+
+    // `❌ Found 2 errors and 1 warning`
+    pub fn summary(&self) -> String {
+        return self.summary_with(&Theme::default());
+    }
+
+    pub fn summary_with(&self, theme: &Theme) -> String {
+        let (errors, warnings) = (self.error_count(), self.warning_count());
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        let severity = if errors == 0 { Severity::Warning } else { Severity::Error };
+
+        let mut summary = if errors == 0 {
+            format!("Found {} warning{}", warnings, plural(warnings))
+        } else {
+            format!("Found {} error{}", errors, plural(errors))
+        };
+        if errors > 0 && warnings > 0 {
+            summary.push_str(&format!(" and {} warning{}", warnings, plural(warnings)));
+        }
+        return format!("{}{}", theme.icon_prefix(severity), theme.paint(&summary, Style::Severity(severity)));
+    }
+
+    // Every diagnostic, or the first `limit` of them, followed by a summary line
+    pub fn pretty(&self, source_code: Option<&SourceCodeContainer>, limit: Option<usize>) -> String {
+        return self.pretty_with(source_code, limit, &Theme::default());
+    }
+
+    pub fn pretty_with(&self, source_code: Option<&SourceCodeContainer>, limit: Option<usize>, theme: &Theme) -> String {
+        let mut output = String::new();
+        if self.errors.is_empty() { return output; }
+
+        let shown = limit.unwrap_or(self.errors.len()).min(self.errors.len());
+        for e in &self.errors[..shown] {
+            output.push_str(&e.pretty_with(source_code, theme));
+            output.push('\n');
+        }
+
+        let total = self.errors.len();
+        output.push_str(&self.summary_with(theme));
+        if shown < total {
+            output.push_str(&format!(", {} more not shown", total - shown));
+        }
+        output.push('\n');
+
+        return output;
+    }
+}
+
+// One error per line
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, e) in self.errors.iter().enumerate() {
+            if idx > 0 { writeln!(f)?; }
+            write!(f, "{}", e)?;
+        }
+        return Ok(());
+    }
+}
+
+impl std::error::Error for Diagnostics {}
+
+impl From<Vec<Error>> for Diagnostics {
+    fn from(errors: Vec<Error>) -> Self {
+        Self { errors }
+    }
+}
@@ -0,0 +1,24 @@
+use super::kind::ErrorKind;
+
+/*
+ * ERROR INDEX
+ *
+ * The long-form explanation of every code, in Markdown. Each one has an
+ * example under `## Wrong` that gets the code and the same example fixed
+ * under `## Correct`. tests/explain.rs compiles all of them.
+ */
+pub fn explanation(kind: &ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::SyntaxError => include_str!("E0001.md"),
+        ErrorKind::StringNotTerminated => include_str!("E0002.md"),
+        ErrorKind::InvalidAssignmentTarget => include_str!("E0003.md"),
+        ErrorKind::LoopControlOutsideLoop => include_str!("E0004.md"),
+        ErrorKind::UnterminatedBlockComment => include_str!("E0005.md"),
+        ErrorKind::InvalidEscape => include_str!("E0006.md"),
+        ErrorKind::IntegerLiteralTooLarge => include_str!("E0007.md"),
+        ErrorKind::LiteralOutOfRange => include_str!("E0008.md"),
+        ErrorKind::UnreachableCode => include_str!("W0001.md"),
+        ErrorKind::WhileTrue => include_str!("W0002.md"),
+        ErrorKind::UnnecessaryParentheses => include_str!("W0003.md"),
+    }
+}
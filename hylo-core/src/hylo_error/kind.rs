@@ -1,14 +1,58 @@
+use super::severity::Severity;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ErrorKind {
     SyntaxError,
-    StringNotTerminated
+    StringNotTerminated,
+    InvalidAssignmentTarget,
+    LoopControlOutsideLoop,
+    UnterminatedBlockComment,
+    InvalidEscape,
+    IntegerLiteralTooLarge,
+    LiteralOutOfRange,
+
+    // Warnings
+    UnreachableCode,
+    WhileTrue,
+    UnnecessaryParentheses
 }
 
 impl ErrorKind {
+    // Every kind, in the order of their codes
+    pub const ALL: [ErrorKind; 11] = [
+        ErrorKind::SyntaxError,
+        ErrorKind::StringNotTerminated,
+        ErrorKind::InvalidAssignmentTarget,
+        ErrorKind::LoopControlOutsideLoop,
+        ErrorKind::UnterminatedBlockComment,
+        ErrorKind::InvalidEscape,
+        ErrorKind::IntegerLiteralTooLarge,
+        ErrorKind::LiteralOutOfRange,
+        ErrorKind::UnreachableCode,
+        ErrorKind::WhileTrue,
+        ErrorKind::UnnecessaryParentheses,
+    ];
+
+    // Every warning, in the order of their codes
+    pub const WARNINGS: [ErrorKind; 3] = [
+        ErrorKind::UnreachableCode,
+        ErrorKind::WhileTrue,
+        ErrorKind::UnnecessaryParentheses,
+    ];
+
     pub fn code(&self) -> &'static str {
         match self {
             ErrorKind::SyntaxError => "E0001",
             ErrorKind::StringNotTerminated=> "E0002",
+            ErrorKind::InvalidAssignmentTarget => "E0003",
+            ErrorKind::LoopControlOutsideLoop => "E0004",
+            ErrorKind::UnterminatedBlockComment => "E0005",
+            ErrorKind::InvalidEscape => "E0006",
+            ErrorKind::IntegerLiteralTooLarge => "E0007",
+            ErrorKind::LiteralOutOfRange => "E0008",
+            ErrorKind::UnreachableCode => "W0001",
+            ErrorKind::WhileTrue => "W0002",
+            ErrorKind::UnnecessaryParentheses => "W0003",
         }
     }
 
@@ -16,7 +60,15 @@ impl ErrorKind {
         match self {
             ErrorKind::SyntaxError => "SyntaxError",
             ErrorKind::StringNotTerminated => "StringNotTerminated",
-
+            ErrorKind::InvalidAssignmentTarget => "InvalidAssignmentTarget",
+            ErrorKind::LoopControlOutsideLoop => "LoopControlOutsideLoop",
+            ErrorKind::UnterminatedBlockComment => "UnterminatedBlockComment",
+            ErrorKind::InvalidEscape => "InvalidEscape",
+            ErrorKind::IntegerLiteralTooLarge => "IntegerLiteralTooLarge",
+            ErrorKind::LiteralOutOfRange => "LiteralOutOfRange",
+            ErrorKind::UnreachableCode => "UnreachableCode",
+            ErrorKind::WhileTrue => "WhileTrue",
+            ErrorKind::UnnecessaryParentheses => "UnnecessaryParentheses",
         }
     }
 
@@ -24,7 +76,45 @@ impl ErrorKind {
         match self {
             ErrorKind::SyntaxError => 1,
             ErrorKind::StringNotTerminated => 1,
+            ErrorKind::InvalidAssignmentTarget => 1,
+            ErrorKind::LoopControlOutsideLoop => 1,
+            ErrorKind::UnterminatedBlockComment => 1,
+            ErrorKind::InvalidEscape => 1,
+            ErrorKind::IntegerLiteralTooLarge => 1,
+            ErrorKind::LiteralOutOfRange => 1,
+            ErrorKind::UnreachableCode => 1,
+            ErrorKind::WhileTrue => 1,
+            ErrorKind::UnnecessaryParentheses => 1,
+        }
+    }
+
+    pub fn default_severity(&self) -> Severity {
+        if self.is_warning() { Severity::Warning } else { Severity::Error }
+    }
 
+    pub fn is_warning(&self) -> bool {
+        return Self::WARNINGS.contains(self);
+    }
+
+    // The name used to allow or deny a warning, e.g. `unreachable_code`
+    pub fn lint_name(&self) -> Option<&'static str> {
+        match self {
+            ErrorKind::UnreachableCode => Some("unreachable_code"),
+            ErrorKind::WhileTrue => Some("while_true"),
+            ErrorKind::UnnecessaryParentheses => Some("unnecessary_parentheses"),
+            _ => None,
         }
     }
-}
\ No newline at end of file
+
+    // `E0001` or `e0001`
+    pub fn from_code(code: &str) -> Option<ErrorKind> {
+        return Self::ALL.iter().find(|kind| kind.code().eq_ignore_ascii_case(code)).cloned();
+    }
+
+    // Looks up a warning by its code (`W0001`) or its lint name (`unreachable_code`)
+    pub fn warning_from(name: &str) -> Option<ErrorKind> {
+        return Self::WARNINGS.iter()
+            .find(|kind| kind.code().eq_ignore_ascii_case(name) || kind.lint_name() == Some(name))
+            .cloned();
+    }
+}
@@ -1,5 +1,19 @@
 pub mod kind;
+pub mod severity;
 pub mod report;
+mod snippet;
+mod width;
+mod unicode_tables;
+pub mod theme;
+pub mod suggestion;
+pub mod diagnostics;
+pub mod output;
+pub mod index;
 
 pub use kind::ErrorKind;
-pub use report::{Error, Span};
\ No newline at end of file
+pub use severity::Severity;
+pub use report::{Details, Error, Label, LabelStyle, Span};
+pub use suggestion::{Applicability, Edit, Suggestion};
+pub use diagnostics::Diagnostics;
+pub use output::ErrorFormat;
+pub use theme::{ColorChoice, Glyphs, Theme};
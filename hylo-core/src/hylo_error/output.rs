@@ -0,0 +1,350 @@
+/*
+ * DIAGNOSTIC OUTPUT
+ *
+ * The formats diagnostics can be printed in:
+ *  - human: the full report with source lines, see `Error::pretty`
+ *  - short: one `file:line:column: severity[code]: message` line each
+ *  - json:  one JSON object per line for each diagnostic
+ *  - sarif: a single SARIF 2.1.0 log for every file
+ *
+ * Lines and columns count chars and start at 1, byte offsets count UTF-8
+ * bytes from the start of the file. Every end is exclusive.
+ */
+use crate::json::Json;
+use crate::lexer::source_code::SourceCodeContainer;
+use super::diagnostics::Diagnostics;
+use super::report::{Error, Label, Span};
+use super::severity::Severity;
+use super::suggestion::Suggestion;
+use super::theme::Theme;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorFormat {
+    #[default]
+    Human,
+    Short,
+    Json,
+    Sarif,
+}
+
+impl ErrorFormat {
+    pub fn from(name: &str) -> Option<Self> {
+        match name {
+            "human" => Some(ErrorFormat::Human),
+            "short" => Some(ErrorFormat::Short),
+            "json" => Some(ErrorFormat::Json),
+            "sarif" => Some(ErrorFormat::Sarif),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorFormat::Human => "human",
+            ErrorFormat::Short => "short",
+            ErrorFormat::Json => "json",
+            ErrorFormat::Sarif => "sarif",
+        }
+    }
+}
+
+/*
+ * Prints the diagnostics of each file in `format`. The error limit and
+ * the theme only apply to the human formats, tools get every diagnostic
+ * as plain text. SARIF always gives a log, an empty one when there is
+ * nothing to report.
+ */
+pub fn render(
+    format: ErrorFormat,
+    files: &[(&Diagnostics, &SourceCodeContainer)],
+    limit: Option<usize>,
+    theme: &Theme
+) -> String {
+    let mut output = String::new();
+    match format {
+        ErrorFormat::Human => {
+            for (diagnostics, source_code) in files {
+                output.push_str(&diagnostics.pretty_with(Some(source_code), limit, theme));
+            }
+        },
+        ErrorFormat::Short => {
+            for (diagnostics, source_code) in files {
+                output.push_str(&short(diagnostics, source_code, limit, theme));
+            }
+        },
+        ErrorFormat::Json => {
+            for (diagnostics, source_code) in files {
+                for e in diagnostics.iter() {
+                    output.push_str(&to_json(e, source_code).to_compact());
+                    output.push('\n');
+                }
+            }
+        },
+        ErrorFormat::Sarif => {
+            output.push_str(&to_sarif(files).to_pretty());
+            output.push('\n');
+        },
+    }
+    return output;
+}
+
+fn short(diagnostics: &Diagnostics, source_code: &SourceCodeContainer, limit: Option<usize>, theme: &Theme) -> String {
+    let mut output = String::new();
+    for e in diagnostics.iter().take(limit.unwrap_or(usize::MAX)) {
+        let file_name = e.file_name.as_deref().unwrap_or("<unknown>");
+        match locate(&e.span, source_code) {
+            Some(location) => output.push_str(&format!("{}:{}:{}: ", file_name, location.line_start, location.column_start)),
+            None => output.push_str(&format!("{}: ", file_name)),
+        }
+        output.push_str(&format!("{}[{}]: {}\n", e.severity.as_str(), e.kind.code(), e.message));
+    }
+    if !diagnostics.is_empty() {
+        output.push_str(&diagnostics.summary_with(theme));
+        output.push('\n');
+    }
+    return output;
+}
+
+// Where a span is, see the header for the units
+struct Location {
+    line_start: usize,
+    column_start: usize,
+    line_end: usize,
+    column_end: usize,
+    byte_start: usize,
+    byte_end: usize,
+}
+
+// None when the span is not in `source_code`
+fn locate(span: &Span, source_code: &SourceCodeContainer) -> Option<Location> {
+    if span.start > span.stop || span.stop >= source_code.total_chars {
+        return None;
+    }
+
+    let (line_start, column_start) = source_code.get_line_and_column(span.start);
+    let (line_end, column_end) = source_code.get_line_and_column(span.stop);
+    return Some(Location {
+        line_start,
+        column_start,
+        line_end,
+        column_end: column_end + 1,
+        byte_start: source_code.byte_offset(span.start),
+        byte_end: source_code.byte_offset(span.stop + 1),
+    });
+}
+
+// Like `locate`, for the exclusive char range of an edit
+fn locate_range(start: usize, end: usize, source_code: &SourceCodeContainer) -> Option<Location> {
+    if start > end || end >= source_code.total_chars {
+        return None;
+    }
+
+    let (line_start, column_start) = source_code.get_line_and_column(start);
+    let (line_end, column_end) = source_code.get_line_and_column(end);
+    return Some(Location {
+        line_start,
+        column_start,
+        line_end,
+        column_end,
+        byte_start: source_code.byte_offset(start),
+        byte_end: source_code.byte_offset(end),
+    });
+}
+
+fn location_to_json(location: Option<Location>) -> Json {
+    let Some(location) = location else { return Json::Null };
+    return Json::object(vec![
+        ("line_start", Json::from(location.line_start)),
+        ("column_start", Json::from(location.column_start)),
+        ("line_end", Json::from(location.line_end)),
+        ("column_end", Json::from(location.column_end)),
+        ("byte_start", Json::from(location.byte_start)),
+        ("byte_end", Json::from(location.byte_end)),
+    ]);
+}
+
+// Labels in other files can't be located without their source
+fn label_in_file(label: &Label, e: &Error) -> bool {
+    return label.file_name.is_none() || label.file_name == e.file_name;
+}
+
+pub fn to_json(e: &Error, source_code: &SourceCodeContainer) -> Json {
+    let labels: Vec<Json> = e.details.labels.iter().map(|label| {
+        let location = if label_in_file(label, e) { locate(&label.span, source_code) } else { None };
+        Json::object(vec![
+            ("style", Json::from(label.style.as_str())),
+            ("message", Json::from(label.message.as_str())),
+            ("file", Json::from(label.file_name.as_deref().or(e.file_name.as_deref()))),
+            ("span", location_to_json(location)),
+        ])
+    }).collect();
+
+    return Json::object(vec![
+        ("code", Json::from(e.kind.code())),
+        ("kind", Json::from(e.kind.as_str())),
+        ("severity", Json::from(e.severity.as_str())),
+        ("message", Json::from(e.message.as_str())),
+        ("file", Json::from(e.file_name.as_deref())),
+        ("span", location_to_json(locate(&e.span, source_code))),
+        ("labels", Json::Array(labels)),
+        ("notes", Json::from(e.details.notes.clone())),
+        ("suggestions", Json::Array(e.details.suggestions.iter().map(|suggestion| suggestion_to_json(suggestion, source_code)).collect())),
+    ]);
+}
+
+fn suggestion_to_json(suggestion: &Suggestion, source_code: &SourceCodeContainer) -> Json {
+    let edits: Vec<Json> = suggestion.edits.iter().map(|edit| Json::object(vec![
+        ("span", location_to_json(locate_range(edit.range.start, edit.range.end, source_code))),
+        ("replacement", Json::from(edit.replacement.as_str())),
+    ])).collect();
+
+    return Json::object(vec![
+        ("message", Json::from(suggestion.message.as_str())),
+        ("applicability", Json::from(suggestion.applicability.as_str())),
+        ("edits", Json::Array(edits)),
+    ]);
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note | Severity::Help => "note",
+    }
+}
+
+fn sarif_location(file_name: Option<&str>, location: Option<Location>, message: Option<&str>) -> Json {
+    let mut physical_location = vec![("artifactLocation", sarif_artifact(file_name))];
+    if let Some(location) = location {
+        physical_location.push(("region", sarif_region(location)));
+    }
+
+    let mut fields = vec![("physicalLocation", Json::object(physical_location))];
+    if let Some(message) = message {
+        fields.push(("message", Json::object(vec![("text", Json::from(message))])));
+    }
+    return Json::object(fields);
+}
+
+fn sarif_artifact(file_name: Option<&str>) -> Json {
+    return Json::object(vec![("uri", Json::from(to_uri(file_name.unwrap_or("<unknown>"))))]);
+}
+
+fn sarif_region(location: Location) -> Json {
+    return Json::object(vec![
+        ("startLine", Json::from(location.line_start)),
+        ("startColumn", Json::from(location.column_start)),
+        ("endLine", Json::from(location.line_end)),
+        ("endColumn", Json::from(location.column_end)),
+        ("byteOffset", Json::from(location.byte_start)),
+        ("byteLength", Json::from(location.byte_end - location.byte_start)),
+    ]);
+}
+
+// Suggestions become fixes, edits that can't be located are left out
+fn sarif_fix(suggestion: &Suggestion, file_name: Option<&str>, source_code: &SourceCodeContainer) -> Json {
+    let replacements: Vec<Json> = suggestion.edits.iter()
+        .filter_map(|edit| {
+            let location = locate_range(edit.range.start, edit.range.end, source_code)?;
+            Some(Json::object(vec![
+                ("deletedRegion", sarif_region(location)),
+                ("insertedContent", Json::object(vec![("text", Json::from(edit.replacement.as_str()))])),
+            ]))
+        })
+        .collect();
+
+    return Json::object(vec![
+        ("description", Json::object(vec![("text", Json::from(suggestion.message.as_str()))])),
+        ("artifactChanges", Json::Array(vec![Json::object(vec![
+            ("artifactLocation", sarif_artifact(file_name)),
+            ("replacements", Json::Array(replacements)),
+        ])])),
+    ]);
+}
+
+// SARIF wants URIs, absolute paths become `file://` URIs and relative ones stay relative
+fn to_uri(path: &str) -> String {
+    let mut uri = String::from(if path.starts_with('/') { "file://" } else { "" });
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => uri.push(byte as char),
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    return uri;
+}
+
+pub fn to_sarif(files: &[(&Diagnostics, &SourceCodeContainer)]) -> Json {
+    // One rule per code that was reported, results point at them by index
+    let mut rules: Vec<Json> = Vec::new();
+    let mut rule_codes: Vec<&str> = Vec::new();
+    let mut results: Vec<Json> = Vec::new();
+
+    for (diagnostics, source_code) in files {
+        for e in diagnostics.iter() {
+            let rule_index = match rule_codes.iter().position(|code| *code == e.kind.code()) {
+                Some(idx) => idx,
+                None => {
+                    rule_codes.push(e.kind.code());
+                    rules.push(Json::object(vec![
+                        ("id", Json::from(e.kind.code())),
+                        ("name", Json::from(e.kind.as_str())),
+                        ("defaultConfiguration", Json::object(vec![
+                            ("level", Json::from(sarif_level(e.kind.default_severity()))),
+                        ])),
+                    ]));
+                    rule_codes.len() - 1
+                }
+            };
+
+            let mut text = e.message.clone();
+            for note in &e.details.notes {
+                text.push_str(&format!("\n{}", note));
+            }
+
+            let related_locations: Vec<Json> = e.details.labels.iter().map(|label| {
+                let location = if label_in_file(label, e) { locate(&label.span, source_code) } else { None };
+                let file_name = label.file_name.as_deref().or(e.file_name.as_deref());
+                sarif_location(file_name, location, Some(&label.message))
+            }).collect();
+
+            let mut result = vec![
+                ("ruleId", Json::from(e.kind.code())),
+                ("ruleIndex", Json::from(rule_index)),
+                ("level", Json::from(sarif_level(e.severity))),
+                ("message", Json::object(vec![("text", Json::from(text))])),
+                ("locations", Json::Array(vec![
+                    sarif_location(e.file_name.as_deref(), locate(&e.span, source_code), None),
+                ])),
+            ];
+            if !related_locations.is_empty() {
+                result.push(("relatedLocations", Json::Array(related_locations)));
+            }
+            if !e.details.suggestions.is_empty() {
+                let fixes = e.details.suggestions.iter()
+                    .map(|suggestion| sarif_fix(suggestion, e.file_name.as_deref(), source_code))
+                    .collect();
+                result.push(("fixes", Json::Array(fixes)));
+            }
+            results.push(Json::object(result));
+        }
+    }
+
+    let driver = Json::object(vec![
+        ("name", Json::from("hylo")),
+        ("version", Json::from(env!("CARGO_PKG_VERSION"))),
+        ("rules", Json::Array(rules)),
+    ]);
+    return Json::object(vec![
+        ("$schema", Json::from(SARIF_SCHEMA)),
+        ("version", Json::from("2.1.0")),
+        ("runs", Json::Array(vec![Json::object(vec![
+            ("tool", Json::object(vec![("driver", driver)])),
+            // Columns count chars, not UTF-16 code units
+            ("columnKind", Json::from("unicodeCodePoints")),
+            ("results", Json::Array(results)),
+        ])])),
+    ]);
+}
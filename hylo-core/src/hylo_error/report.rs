@@ -1,6 +1,10 @@
-use std::process;
+use std::fmt;
 use crate::lexer::source_code::SourceCodeContainer;
 use super::kind::ErrorKind;
+use super::severity::Severity;
+use super::snippet;
+use super::suggestion::Suggestion;
+use super::theme::{Glyph, Style, Theme};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Span {
@@ -8,31 +12,77 @@ pub struct Span {
     pub stop: usize
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LabelStyle {
+    // What the error is about, underlined with `^`
+    Primary,
+    // Context for it, underlined with `-`
+    Secondary,
+}
+
+impl LabelStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LabelStyle::Primary => "primary",
+            LabelStyle::Secondary => "secondary",
+        }
+    }
+
+    pub fn marker(&self) -> char {
+        match self {
+            LabelStyle::Primary => '^',
+            LabelStyle::Secondary => '-',
+        }
+    }
+}
+
+// A span with a message of its own
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub style: LabelStyle,
+    pub span: Span,
+    // None when the span is in the file of the error
+    pub file_name: Option<String>,
+    pub message: String,
+}
+
+// What most errors leave empty, boxed to keep `Result<_, Error>` small
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Details {
+    // Further spans, beside the primary one
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub suggestions: Vec<Suggestion>
+}
+
 /*
 Error
 */
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Error {
     pub kind: ErrorKind,
+    pub severity: Severity,
+    // The primary span, `message` is its label
     pub span: Span,
     pub file_name: Option<String>,
     pub message: String,
-    pub notes: Vec<String>
+    pub details: Box<Details>
 }
 
 impl Error {
     pub fn new(error_kind: ErrorKind, span: Span, file_name: Option<&str>) -> Self {
         Error {
+            severity: error_kind.default_severity(),
             kind: error_kind,
             span: span,
             file_name: file_name.map(|s| s.to_string()),
             message: String::new(),
-            notes: Vec::new()
+            details: Box::default()
         }
     }
 
     pub fn add_note(mut self, note: &str) -> Self {
-        self.notes.push(String::from(note));
+        self.details.notes.push(String::from(note));
         return self;
     }
 
@@ -41,183 +91,145 @@ impl Error {
         return self;
     }
 
-    pub fn panic(&self, source_code:Option<&SourceCodeContainer>) -> ! {
-        let pretty_error = self.pretty(source_code);
-        eprintln!("{}", pretty_error);
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        return self;
+    }
 
-        process::exit(self.kind.exit_code());
+    // Points at another span of the same file, e.g. where a bracket was opened
+    pub fn add_label(mut self, span: Span, msg: &str) -> Self {
+        self.details.labels.push(Label { style: LabelStyle::Secondary, span, file_name: None, message: String::from(msg) });
+        return self;
+    }
+
+    // Points at a span of another file
+    pub fn add_label_in(mut self, file_name: &str, span: Span, msg: &str) -> Self {
+        self.details.labels.push(Label {
+            style: LabelStyle::Secondary,
+            span,
+            file_name: Some(String::from(file_name)),
+            message: String::from(msg)
+        });
+        return self;
+    }
+
+    pub fn add_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.details.suggestions.push(suggestion);
+        return self;
     }
 
     pub fn pretty(&self, source_code:Option<&SourceCodeContainer>) -> String {
+        return self.pretty_with(source_code, &Theme::default());
+    }
+
+    pub fn pretty_with(&self, source_code:Option<&SourceCodeContainer>, theme: &Theme) -> String {
         let mut output = String::new();
 
+        // A span that doesn't belong to this source can't be shown in it
+        let is_valid = |span: &Span, source_code: &SourceCodeContainer| {
+            span.start <= span.stop && span.stop < source_code.total_chars
+        };
+        let source_code = source_code.filter(|source_code| is_valid(&self.span, source_code));
+
         let file_name = self.file_name.as_deref().unwrap_or("<unknown>");
+        let in_this_file = |label: &&Label| {
+            label.file_name.is_none() || label.file_name == self.file_name
+        };
+        let gutter = theme.paint("   |", Style::Gutter);
+        let primary = Style::Severity(self.severity);
+
         if source_code.is_none() {
             // Header
-            output.push_str(&format!("❌ ERROR [{}]: {}\n", self.kind.code(), self.kind.as_str()));
+            output.push_str(&self.header(theme));
             output.push_str(&format!("File: {} | Code: {}\n", file_name, self.kind.code()));
-            output.push_str("   |\n");
-            output.push_str(&format!("   | ❗ {}\n", self.message));
+            output.push_str(&format!("{}\n", gutter));
+            output.push_str(&format!("{} {} {}\n", gutter, theme.bullet(Glyph::Message), theme.paint(&self.message, primary)));
+            for label in &self.details.labels {
+                let marker = theme.paint(&label.style.marker().to_string(), Style::Secondary);
+                match &label.file_name {
+                    Some(label_file) if !in_this_file(&label) => {
+                        output.push_str(&format!("{} {} {} (in {})\n", gutter, marker, label.message, label_file));
+                    },
+                    _ => output.push_str(&format!("{} {} {}\n", gutter, marker, label.message)),
+                }
+            }
 
             // Note
-            for note in &self.notes {
-                output.push_str(&format!("   | 💡 {}\n", note));
+            for note in &self.details.notes {
+                output.push_str(&format!("{} {} {}\n", gutter, theme.bullet(Glyph::Hint), note));
+            }
+            for suggestion in &self.details.suggestions {
+                output.push_str(&format!("{} {} {}\n", gutter, theme.bullet(Glyph::Suggestion), suggestion.message));
             }
-        } else {
-            let source_code = source_code.unwrap();
-            
+        } else if let Some(source_code) = source_code {
             // 1. Error header
-            output.push_str(&format!("❌ ERROR [{}]: {}\n", self.kind.code(), self.kind.as_str()));
-
+            output.push_str(&self.header(theme));
 
             // 2. Location info (file:line:column)
             let (line_no, column_no) = source_code.get_line_and_column(self.span.start);
-            output.push_str(&format!("   --> {}:{}:{}\n",
+            output.push_str(&format!("{} {}:{}:{}\n",
+                theme.paint("   -->", Style::Gutter),
                 file_name,
                 line_no,
                 column_no
             ));
 
-            // 3. Add the margin bar
-            output.push_str("    |\n");
-
-            // 4. Get source line
-            let error_source = ErrorSourceContainer::from(&source_code, self.span.start, self.span.stop);
+            // 3. Source lines with an underline per label
+            let mut labels = vec![Label {
+                style: LabelStyle::Primary,
+                span: self.span,
+                file_name: None,
+                message: self.message.clone()
+            }];
+            labels.extend(self.details.labels.iter()
+                .filter(in_this_file)
+                .filter(|label| is_valid(&label.span, source_code))
+                .cloned());
+            output.push_str(&snippet::render(source_code, &labels, self.severity, theme));
+
+            // 4. Labels in other files, their source isn't available here
+            for label in self.details.labels.iter().filter(|label| !in_this_file(label)) {
+                let file = label.file_name.as_deref().unwrap_or(file_name);
+                output.push_str(&format!("{} {}: {}\n", theme.paint("   :::", Style::Gutter), file, label.message));
+            }
 
-            // 5. Print line number + code and underline error
-            for (i, error_line) in error_source.lines.iter().enumerate() {
-                output.push_str(&format!("{:>3} | {}\n", line_no+i, error_line));
+            // 5. Notes
+            for note in &self.details.notes {
+                output.push_str(&format!("{} {}\n", theme.paint(theme.heading(Glyph::Hint), Style::Bold), note));
+            }
 
-                
-                if error_source.start.0 + 1 == line_no+i {
-                    let span_len = if error_source.end.1 > error_source.start.1 { error_source.end.1 - error_source.start.1} else { 1 };
-                    
-                    output.push_str("    | ");
-                    output.push_str(&" ".repeat(error_source.start.1));
-                    output.push_str(&"^".repeat(span_len));
-                    output.push_str(&format!(" {}\n", self.message));
+            // 6. Suggestions, as the lines they change
+            for suggestion in &self.details.suggestions {
+                output.push_str(&format!("{} {}\n", theme.paint(theme.heading(Glyph::Suggestion), Style::Bold), suggestion.message));
+                if let Some(diff) = suggestion.diff_with(source_code, theme) {
+                    output.push_str(&diff);
                 }
             }
-            
-            // 6. Notes
-            for note in &self.notes {
-                output.push_str(&format!("💡 Hint: {}\n", note));
-            }
         }
 
         return output;
     }
-}
-
-struct ErrorSourceContainer {
-    lines: Vec<String>,
-    start: (usize, usize),
-    end: (usize, usize)
-}
-
-impl ErrorSourceContainer {
-    pub fn from(source_code: &SourceCodeContainer, start_pos: usize, end_pos:usize) -> Self {
-        if start_pos > end_pos || end_pos > source_code.total_chars-1 {
-            panic!("Expected a valid start or end pos");
-        }
 
-        let mut error_source_container = Self {
-            lines: Vec::new(),
-            start: (0, 0),
-            end: (0, 0)
-        };
-        
-        let mut rel_start = (-1, -1);
-        let mut rel_end_idx = -1;
-        for (idx, l) in source_code.source_code.iter().enumerate() {
-            if start_pos > l.end_pos {
-                continue
-            } else if end_pos < l.start_pos {
-                rel_end_idx = idx as i32;
-                break;
-            } else {
-                error_source_container.lines.push(l.line.iter().collect());
-                if rel_start.0 == -1 {
-                    rel_start.0 = idx as i32;
-                    rel_start.1 = l.get_relative_pos(start_pos) as i32;
-                }
-            }
-        }
-
-        error_source_container.start = (rel_start.0 as usize, rel_start.1 as usize);
-        error_source_container.end = (rel_end_idx as usize, error_source_container.start.1 + (end_pos - start_pos));
-        error_source_container.trim();
-
-        return error_source_container;
+    // `❌ ERROR [E0001]: SyntaxError`
+    fn header(&self, theme: &Theme) -> String {
+        let severity = format!("{} [{}]", self.severity.as_str().to_uppercase(), self.kind.code());
+        return format!("{}{}{}\n",
+            theme.icon_prefix(self.severity),
+            theme.paint(&severity, Style::Severity(self.severity)),
+            theme.paint(&format!(": {}", self.kind.as_str()), Style::Bold)
+        );
     }
+}
 
-    pub fn trim(&mut self) {
-        if self.lines.is_empty() { return; }
-
-        // Helper to find first non-whitespace byte index (or len if none)
-        let first_non_ws = |s: &str| {
-            s.char_indices()
-                .find(|&(_, c)| !c.is_whitespace())
-                .map(|(i, _)| i)
-                .unwrap_or(s.len())
-        };
-
-        // Helper to find last non-whitespace byte index (end-exclusive),
-        // or 0 if none.
-        let last_non_ws_end = |s: &str| {
-            s.char_indices()
-                .rev()
-                .find(|&(_, c)| !c.is_whitespace())
-                .map(|(i, c)| i + c.len_utf8())
-                .unwrap_or(0)
-        };
-
-        // Special case: single line -> trim both ends
-        if self.lines.len() == 1 {
-            let s = &self.lines[0];
-            let start = first_non_ws(s);
-            let end = last_non_ws_end(s);
-
-            let new = if start >= end { String::new() } else { s[start..end].to_string() };
-            let new_len = new.len();
-
-            // adjust byte offsets (assumes start.1 and end.1 are byte offsets)
-            self.start.1 = self.start.1.saturating_sub(start).min(new_len);
-            self.end.1 = self.end.1.saturating_sub(start).min(new_len);
-
-            self.lines[0] = new;
-            return;
-        }
-
-        // --- Trim first line (leading whitespace) ---
-        {
-            let first = &self.lines[0];
-            let trimmed_start = first_non_ws(first); // byte offset, len if all whitespace
-            if trimmed_start > 0 {
-                self.lines[0] = first[trimmed_start..].to_string();
-                // adjust start column (byte-offset)
-                self.start.1 = self.start.1.saturating_sub(trimmed_start).min(self.lines[0].len());
-            }
-        }
-
-        // --- Trim middle lines (trim both ends) ---
-        if self.lines.len() > 2 {
-            for i in 1..self.lines.len() - 1 {
-                self.lines[i] = self.lines[i].trim().to_string(); // trim() removes whitespace incl. '\n', '\r'
-            }
-        }
-
-        // --- Trim last line (trailing whitespace) ---
-        {
-            let last_idx = self.lines.len() - 1;
-            let last = &self.lines[last_idx];
-            let trimmed_end = last_non_ws_end(last); // end-exclusive byte index
-            if trimmed_end < last.len() {
-                self.lines[last_idx] = last[..trimmed_end].to_string();
-                // clamp end column to the new length
-                self.end.1 = self.end.1.min(trimmed_end);
-            }
+// `E0001 SyntaxError in test.hy: message`, the one line form without source code
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.kind.code(), self.kind.as_str())?;
+        if let Some(file_name) = &self.file_name {
+            write!(f, " in {}", file_name)?;
         }
+        return write!(f, ": {}", self.message);
     }
+}
 
-}
\ No newline at end of file
+impl std::error::Error for Error {}
@@ -0,0 +1,150 @@
+use crate::lexer::source_code::SourceCodeContainer;
+use super::report::{Label, LabelStyle};
+use super::severity::Severity;
+use super::theme::{Style, Theme};
+use super::width;
+
+/*
+ * SOURCE SNIPPETS
+ *
+ * Prints the source lines the labels point at, each followed by an
+ * underline per label on it. A label over several lines is underlined
+ * to the end of its first line and from the start of its last one, the
+ * lines in between are left out. The common indentation is removed.
+ *
+ * Labels point at chars but the underlines are placed by display column,
+ * so tabs, wide chars and combining marks before them keep them aligned.
+ * Tabs are printed as spaces, as wide as the terminal would show them.
+ */
+
+// The part of a label on one line, `start` and `end` are char offsets, `end` exclusive
+struct Segment<'a> {
+    line_idx: usize,
+    start: usize,
+    end: usize,
+    style: LabelStyle,
+    // Only the last segment of a label carries its message
+    message: &'a str,
+}
+
+// Primary labels take the colour of the severity
+pub fn render(source_code: &SourceCodeContainer, labels: &[Label], severity: Severity, theme: &Theme) -> String {
+    let mut segments = Vec::new();
+    for label in labels {
+        split_label(source_code, label, &mut segments);
+    }
+    segments.sort_by_key(|segment| (segment.line_idx, segment.start, segment.style));
+
+    let mut line_idxs: Vec<usize> = segments.iter().map(|segment| segment.line_idx).collect();
+    line_idxs.dedup();
+
+    let lines: Vec<Vec<char>> = line_idxs.iter().map(|&line_idx| line_text(source_code, line_idx)).collect();
+    let columns: Vec<Vec<usize>> = lines.iter().map(|line| width::columns(line)).collect();
+    // In display columns
+    let indent = lines.iter().zip(&columns)
+        .filter(|(line, _)| line.iter().any(|c| !c.is_whitespace()))
+        .map(|(line, columns)| columns[line.iter().take_while(|c| c.is_whitespace()).count()])
+        .min()
+        .unwrap_or(0);
+
+    let last_line_no = line_idxs.last().map(|line_idx| line_idx + 1).unwrap_or(0);
+    let width = last_line_no.to_string().len().max(3);
+
+    let gutter = theme.paint(&format!("{} |", " ".repeat(width)), Style::Gutter);
+    let mut output = String::new();
+    output.push_str(&format!("{}\n", gutter));
+    for (n, &line_idx) in line_idxs.iter().enumerate() {
+        if n > 0 && line_idx > line_idxs[n - 1] + 1 {
+            output.push_str(&format!("{}\n", theme.paint(&format!("{:>width$}", "...", width = width), Style::Gutter)));
+        }
+
+        let text = skip_columns(&width::expand_tabs(&lines[n]), indent);
+        let line_no = theme.paint(&format!("{:>width$} |", line_idx + 1, width = width), Style::Gutter);
+        output.push_str(&format!("{} {}\n", line_no, text.trim_end()));
+
+        for segment in segments.iter().filter(|segment| segment.line_idx == line_idx) {
+            let start = column_at(&columns[n], segment.start);
+            let end = end_column_at(&columns[n], segment.end.max(segment.start + 1) - 1);
+            let len = end.saturating_sub(start).max(1);
+            let start = start.saturating_sub(indent);
+            let style = match segment.style {
+                LabelStyle::Primary => Style::Severity(severity),
+                LabelStyle::Secondary => Style::Secondary,
+            };
+
+            let mut underline = segment.style.marker().to_string().repeat(len);
+            if !segment.message.is_empty() {
+                underline.push_str(&format!(" {}", segment.message));
+            }
+            output.push_str(&format!("{} {}{}\n", gutter, " ".repeat(start), theme.paint(&underline, style)));
+        }
+    }
+
+    return output;
+}
+
+fn split_label<'a>(source_code: &SourceCodeContainer, label: &'a Label, segments: &mut Vec<Segment<'a>>) {
+    let (start_line, start_col) = position(source_code, label.span.start);
+    let (end_line, end_col) = position(source_code, label.span.stop);
+
+    if start_line == end_line {
+        segments.push(Segment {
+            line_idx: start_line,
+            start: start_col,
+            end: end_col + 1,
+            style: label.style,
+            message: &label.message,
+        });
+        return;
+    }
+
+    let first_line_len = line_text(source_code, start_line).len();
+    segments.push(Segment {
+        line_idx: start_line,
+        start: start_col,
+        end: first_line_len.max(start_col + 1),
+        style: label.style,
+        message: "",
+    });
+
+    let last_line = line_text(source_code, end_line);
+    segments.push(Segment {
+        line_idx: end_line,
+        start: last_line.iter().take_while(|c| c.is_whitespace()).count().min(end_col),
+        end: end_col + 1,
+        style: label.style,
+        message: &label.message,
+    });
+}
+
+// The display column of a char, past the end of the line every char takes one
+fn column_at(columns: &[usize], idx: usize) -> usize {
+    let end = columns.len() - 1;
+    return if idx <= end { columns[idx] } else { columns[end] + idx - end };
+}
+
+// The display column right after a char
+fn end_column_at(columns: &[usize], idx: usize) -> usize {
+    return if idx + 1 < columns.len() { width::end_column(columns, idx) } else { column_at(columns, idx) + 1 };
+}
+
+// `text` without its first `count` columns, which are all whitespace
+fn skip_columns(text: &str, count: usize) -> String {
+    let mut skipped = 0;
+    return text.chars().skip_while(|&c| {
+        let skip = skipped < count;
+        skipped += width::char_width(c);
+        skip
+    }).collect();
+}
+
+// Zero based line index and column of a char position
+fn position(source_code: &SourceCodeContainer, pos: usize) -> (usize, usize) {
+    let (line_no, column_no) = source_code.get_line_and_column(pos);
+    return (line_no - 1, column_no - 1);
+}
+
+// The chars of a line, without its newline
+fn line_text(source_code: &SourceCodeContainer, line_idx: usize) -> Vec<char> {
+    return source_code.line(line_idx).to_vec();
+}
@@ -0,0 +1,147 @@
+use std::ops::Range;
+use crate::lexer::source_code::SourceCodeContainer;
+use super::theme::{Style, Theme};
+
+// How sure a suggestion is to give the code that was meant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Applicability {
+    // Safe to apply without looking, `hylo fix` applies these
+    MachineApplicable,
+    // Fixes the error but may not be what was meant
+    MaybeIncorrect,
+    // Contains text such as `<name>` that has to be filled in
+    HasPlaceholders,
+}
+
+impl Applicability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Applicability::MachineApplicable => "machine-applicable",
+            Applicability::MaybeIncorrect => "maybe-incorrect",
+            Applicability::HasPlaceholders => "has-placeholders",
+        }
+    }
+}
+
+// Replaces the chars in `range` with `replacement`, an empty range inserts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+// A change to the source that fixes a diagnostic, made of one or more edits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub message: String,
+    pub edits: Vec<Edit>,
+    pub applicability: Applicability,
+}
+
+impl Suggestion {
+    pub fn new(message: &str, range: Range<usize>, replacement: &str, applicability: Applicability) -> Self {
+        Self {
+            message: String::from(message),
+            edits: vec![Edit { range, replacement: String::from(replacement) }],
+            applicability,
+        }
+    }
+
+    pub fn add_edit(mut self, range: Range<usize>, replacement: &str) -> Self {
+        self.edits.push(Edit { range, replacement: String::from(replacement) });
+        return self;
+    }
+
+    // First and last char touched by the edits
+    fn range(&self) -> Range<usize> {
+        let start = self.edits.iter().map(|edit| edit.range.start).min().unwrap_or(0);
+        let end = self.edits.iter().map(|edit| edit.range.end).max().unwrap_or(0);
+        return start..end;
+    }
+
+    /*
+     * The lines the suggestion changes, before and after:
+     *
+     *   3 -     second;
+     *   3 +     second);
+     */
+    pub fn diff(&self, source_code: &SourceCodeContainer) -> Option<String> {
+        return self.diff_with(source_code, &Theme::default());
+    }
+
+    // The diff with removed lines in red and added ones in green
+    pub fn diff_with(&self, source_code: &SourceCodeContainer, theme: &Theme) -> Option<String> {
+        let range = self.range();
+        if range.start > range.end || range.end >= source_code.total_chars {
+            return None;
+        }
+
+        let first_line = source_code.get_line_and_column(range.start).0 - 1;
+        let last_line = source_code.get_line_and_column(range.end.saturating_sub(1).max(range.start)).0 - 1;
+        let start_pos = source_code.line_span(first_line).0;
+        let end_pos = source_code.line_span(last_line).1;
+
+        // The lines without their final newline
+        let old_text: Vec<char> = (start_pos..end_pos).filter_map(|pos| source_code.char_at(pos)).collect();
+        let edits: Vec<Edit> = self.edits.iter().map(|edit| Edit {
+            range: edit.range.start - start_pos..edit.range.end - start_pos,
+            replacement: edit.replacement.clone(),
+        }).collect();
+        let new_text = apply_edits(&old_text, &edits);
+
+        let width = (last_line + 1 + new_text.matches('\n').count()).to_string().len().max(3);
+        let mut output = String::new();
+        for (idx, line) in old_text.iter().collect::<String>().split('\n').enumerate() {
+            let line = format!("{:>width$} - {}", first_line + 1 + idx, line.trim_end(), width = width);
+            output.push_str(&format!("{}\n", theme.paint(&line, Style::Removed)));
+        }
+        for (idx, line) in new_text.split('\n').enumerate() {
+            let line = format!("{:>width$} + {}", first_line + 1 + idx, line.trim_end(), width = width);
+            output.push_str(&format!("{}\n", theme.paint(&line, Style::Added)));
+        }
+        return Some(output);
+    }
+}
+
+fn apply_edits(text: &[char], edits: &[Edit]) -> String {
+    let mut edits: Vec<&Edit> = edits.iter().collect();
+    edits.sort_by_key(|edit| edit.range.start);
+
+    let mut output = String::new();
+    let mut pos = 0;
+    for edit in edits {
+        output.extend(&text[pos..edit.range.start]);
+        output.push_str(&edit.replacement);
+        pos = edit.range.end;
+    }
+    output.extend(&text[pos..]);
+    return output;
+}
+
+/*
+ * Applies the suggestions to `source`, skipping any that overlaps one
+ * applied before it. Returns the new source and the applied suggestions.
+ */
+pub fn apply_suggestions<'a>(source: &str, suggestions: &[&'a Suggestion]) -> (String, Vec<&'a Suggestion>) {
+    let chars: Vec<char> = source.chars().collect();
+    let mut suggestions: Vec<&Suggestion> = suggestions.iter()
+        .filter(|suggestion| suggestion.range().end <= chars.len())
+        .cloned()
+        .collect();
+    suggestions.sort_by_key(|suggestion| suggestion.range().start);
+
+    let mut edits: Vec<Edit> = Vec::new();
+    let mut applied = Vec::new();
+    for suggestion in suggestions {
+        let overlaps = suggestion.edits.iter().any(|new| edits.iter().any(|old| {
+            new.range.start < old.range.end && old.range.start < new.range.end
+                || new.range.start == old.range.start
+        }));
+        if overlaps { continue; }
+
+        edits.extend(suggestion.edits.iter().cloned());
+        applied.push(suggestion);
+    }
+
+    return (apply_edits(&chars, &edits), applied);
+}
@@ -0,0 +1,174 @@
+use super::severity::Severity;
+
+/*
+ * THEMES
+ *
+ * How the human readable reports look. The glyphs are either the emoji
+ * the reports have always used or plain ASCII for CI logs and consoles
+ * that can't show emoji. Colour is independent of the glyphs and off by
+ * default, the command line turns it on when stderr is a terminal.
+ */
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Glyphs {
+    #[default]
+    Emoji,
+    Ascii,
+}
+
+impl Glyphs {
+    pub fn from(name: &str) -> Option<Self> {
+        match name {
+            "emoji" => Some(Glyphs::Emoji),
+            "ascii" => Some(Glyphs::Ascii),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Glyphs::Emoji => "emoji",
+            Glyphs::Ascii => "ascii",
+        }
+    }
+}
+
+// `--color=auto|always|never`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn from(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(ColorChoice::Auto),
+            "always" => Some(ColorChoice::Always),
+            "never" => Some(ColorChoice::Never),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        }
+    }
+
+    /*
+     * Whether to colour output going to a stream. Auto colours terminals
+     * unless `NO_COLOR` is set to anything but an empty string, see
+     * https://no-color.org. An explicit `always` wins over `NO_COLOR`.
+     */
+    pub fn enabled(&self, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                is_terminal && !no_color
+            },
+        }
+    }
+}
+
+// The parts of a report that get a colour of their own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Severity(Severity),
+    // Line numbers, `|` and `-->`
+    Gutter,
+    // Underlines and messages of secondary labels
+    Secondary,
+    // Lines of a suggestion before and after the change
+    Removed,
+    Added,
+    Bold,
+}
+
+impl Style {
+    fn ansi_code(&self) -> &'static str {
+        match self {
+            Style::Severity(Severity::Error) => "1;31",
+            Style::Severity(Severity::Warning) => "1;33",
+            Style::Severity(Severity::Note) => "1;32",
+            Style::Severity(Severity::Help) => "1;36",
+            Style::Gutter | Style::Secondary => "1;34",
+            Style::Removed => "31",
+            Style::Added => "32",
+            Style::Bold => "1",
+        }
+    }
+}
+
+// The markers in front of the lines below a report's header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyph {
+    Message,
+    Hint,
+    Suggestion,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Theme {
+    pub glyphs: Glyphs,
+    pub color: bool,
+}
+
+impl Theme {
+    pub fn new(glyphs: Glyphs, color: bool) -> Self {
+        Self { glyphs, color }
+    }
+
+    pub fn ascii() -> Self {
+        Self { glyphs: Glyphs::Ascii, color: false }
+    }
+
+    // Wraps `text` in the ANSI codes of `style` when colour is on
+    pub fn paint(&self, text: &str, style: Style) -> String {
+        if !self.color || text.is_empty() {
+            return String::from(text);
+        }
+        return format!("\x1b[{}m{}\x1b[0m", style.ansi_code(), text);
+    }
+
+    // Printed in front of headers and summaries, ASCII has none
+    pub fn icon(&self, severity: Severity) -> &'static str {
+        match self.glyphs {
+            Glyphs::Emoji => severity.icon(),
+            Glyphs::Ascii => "",
+        }
+    }
+
+    // `❌ ` or nothing, to put in front of a line
+    pub fn icon_prefix(&self, severity: Severity) -> String {
+        let icon = self.icon(severity);
+        if icon.is_empty() { String::new() } else { format!("{} ", icon) }
+    }
+
+    // Marks the lines of a report without source code
+    pub fn bullet(&self, glyph: Glyph) -> &'static str {
+        match (self.glyphs, glyph) {
+            (Glyphs::Emoji, Glyph::Message) => "❗",
+            (Glyphs::Emoji, Glyph::Hint) => "💡",
+            (Glyphs::Emoji, Glyph::Suggestion) => "🔧",
+            (Glyphs::Ascii, Glyph::Message) => ">",
+            (Glyphs::Ascii, Glyph::Hint) => "hint:",
+            (Glyphs::Ascii, Glyph::Suggestion) => "suggestion:",
+        }
+    }
+
+    // Starts the hint and suggestion lines below the source
+    pub fn heading(&self, glyph: Glyph) -> &'static str {
+        match (self.glyphs, glyph) {
+            (Glyphs::Emoji, Glyph::Hint) => "💡 Hint:",
+            (Glyphs::Emoji, Glyph::Suggestion) => "🔧 Suggestion:",
+            _ => self.bullet(glyph),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+/*
+ * DISPLAY WIDTH
+ *
+ * How many terminal columns the chars of a source line take, so that the
+ * underlines of a snippet line up with the text above them. The line is
+ * split into grapheme clusters following UAX #29, and a cluster takes:
+ *  - up to the next multiple of TAB_WIDTH for a tab
+ *  - two columns for an emoji, however many chars it is made of
+ *  - otherwise the widths of its chars added up: two for East Asian wide
+ *    and fullwidth chars, none for combining marks, format chars and the
+ *    Hangul vowels and final consonants after a leading consonant, one
+ *    for everything else, spacing marks included
+ *
+ * The tables are generated from the Unicode character database by
+ * scripts/unicode_tables.py.
+ */
+use super::unicode_tables::{GraphemeBreak, EXTENDED_PICTOGRAPHIC, GRAPHEME_BREAK, WIDE, ZERO_WIDTH};
+
+pub const TAB_WIDTH: usize = 4;
+
+const EMOJI_PRESENTATION: char = '\u{FE0F}';
+
+fn in_table(c: char, table: &[(u32, u32)]) -> bool {
+    let c = c as u32;
+    return table.binary_search_by(|&(start, end)| {
+        if end < c {
+            std::cmp::Ordering::Less
+        } else if start > c {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    }).is_ok();
+}
+
+fn grapheme_break(c: char) -> GraphemeBreak {
+    let c = c as u32;
+    let found = GRAPHEME_BREAK.binary_search_by(|&(start, end, _)| {
+        if end < c {
+            std::cmp::Ordering::Less
+        } else if start > c {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    });
+    match found {
+        Ok(idx) => return GRAPHEME_BREAK[idx].2,
+        Err(_) => return GraphemeBreak::Other,
+    }
+}
+
+fn is_pictographic(c: char) -> bool {
+    return in_table(c, EXTENDED_PICTOGRAPHIC);
+}
+
+// Width of a char on its own, tabs aside
+pub fn char_width(c: char) -> usize {
+    if in_table(c, ZERO_WIDTH) {
+        return 0;
+    }
+    if in_table(c, WIDE) {
+        return 2;
+    }
+    return 1;
+}
+
+// Whether a grapheme cluster ends right before `line[idx]`, by the rules of UAX #29
+fn is_boundary(line: &[char], idx: usize) -> bool {
+    use GraphemeBreak::*;
+
+    match (grapheme_break(line[idx - 1]), grapheme_break(line[idx])) {
+        // GB3 to GB5, "\r\n" stays together and other controls stand alone
+        (CarriageReturn, LineFeed) => return false,
+        (CarriageReturn | LineFeed | Control, _) | (_, CarriageReturn | LineFeed | Control) => return true,
+        // GB6 to GB8, Hangul syllables written as jamo
+        (HangulL, HangulL | HangulV | HangulLV | HangulLVT) => return false,
+        (HangulLV | HangulV, HangulV | HangulT) => return false,
+        (HangulLVT | HangulT, HangulT) => return false,
+        // GB9 to GB9b, marks stay with the char before them
+        (_, Extend | ZeroWidthJoiner | SpacingMark) => return false,
+        (Prepend, _) => return false,
+        // GB11, emoji joined into one
+        (ZeroWidthJoiner, _) if is_pictographic(line[idx]) => return !follows_pictographic(line, idx - 1),
+        // GB12 and GB13, regional indicators pair up into flags
+        (RegionalIndicator, RegionalIndicator) => return regional_run(line, idx).is_multiple_of(2),
+        _ => return true,
+    }
+}
+
+// Whether the char at `idx` comes after an emoji and the marks on it
+fn follows_pictographic(line: &[char], idx: usize) -> bool {
+    return line[..idx].iter().rev()
+        .find(|&&c| grapheme_break(c) != GraphemeBreak::Extend)
+        .is_some_and(|&c| is_pictographic(c));
+}
+
+// Number of regional indicators right before `idx`
+fn regional_run(line: &[char], idx: usize) -> usize {
+    return line[..idx].iter().rev().take_while(|&&c| grapheme_break(c) == GraphemeBreak::RegionalIndicator).count();
+}
+
+// Columns a grapheme cluster takes, tabs aside
+fn cluster_width(cluster: &[char]) -> usize {
+    let width = if is_pictographic(cluster[0]) {
+        char_width(cluster[0])
+    } else {
+        cluster.iter().map(|&c| char_width(c)).sum()
+    };
+
+    // A text style char shown as emoji becomes wide
+    if cluster.contains(&EMOJI_PRESENTATION) {
+        return width.max(2);
+    }
+    return width;
+}
+
+/*
+ * The display column every char of `line` starts at, followed by the
+ * column the line ends at. The chars of a grapheme cluster all start at
+ * its first column. For `a🇫🇷b`, where the flag is two regional indicators:
+ *
+ *   a  🇫  🇷  b  end
+ *   0  1  1  3  4
+ */
+pub fn columns(line: &[char]) -> Vec<usize> {
+    let mut columns = Vec::with_capacity(line.len() + 1);
+    let mut column = 0;
+    let mut start = 0;
+
+    while start < line.len() {
+        let end = (start + 1..line.len()).find(|&idx| is_boundary(line, idx)).unwrap_or(line.len());
+        let cluster = &line[start..end];
+        columns.extend(std::iter::repeat_n(column, cluster.len()));
+        column += if cluster == ['\t'] { TAB_WIDTH - column % TAB_WIDTH } else { cluster_width(cluster) };
+        start = end;
+    }
+
+    columns.push(column);
+    return columns;
+}
+
+// The column right after the grapheme cluster of the char at `idx`
+pub fn end_column(columns: &[usize], idx: usize) -> usize {
+    return columns[idx + 1..].iter().find(|&&column| column > columns[idx]).cloned().unwrap_or(columns[idx]);
+}
+
+// `line` as it is printed, tabs replaced by spaces up to the next stop
+pub fn expand_tabs(line: &[char]) -> String {
+    let columns = columns(line);
+    let mut output = String::new();
+    for (idx, &c) in line.iter().enumerate() {
+        if c == '\t' {
+            output.push_str(&" ".repeat(columns[idx + 1] - columns[idx]));
+        } else {
+            output.push(c);
+        }
+    }
+    return output;
+}
@@ -0,0 +1,148 @@
+/*
+ * JSON WRITER
+ *
+ * Just enough JSON to print machine readable output. Object fields keep
+ * the order they were added in, so the output is stable.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    // Already formatted, so that integers of any width stay exact
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object(fields: Vec<(&str, Json)>) -> Self {
+        Json::Object(fields.into_iter().map(|(key, value)| (String::from(key), value)).collect())
+    }
+
+    // On a single line
+    pub fn to_compact(&self) -> String {
+        let mut output = String::new();
+        self.write(&mut output, None, 0);
+        return output;
+    }
+
+    // Indented by two spaces per level
+    pub fn to_pretty(&self) -> String {
+        let mut output = String::new();
+        self.write(&mut output, Some(2), 0);
+        return output;
+    }
+
+    fn write(&self, output: &mut String, indent: Option<usize>, depth: usize) {
+        let newline = |output: &mut String, depth: usize| {
+            if let Some(width) = indent {
+                output.push('\n');
+                output.push_str(&" ".repeat(width * depth));
+            }
+        };
+
+        match self {
+            Json::Null => output.push_str("null"),
+            Json::Bool(value) => output.push_str(if *value { "true" } else { "false" }),
+            Json::Number(number) => output.push_str(number),
+            Json::String(text) => write_string(output, text),
+            Json::Array(items) => {
+                if items.is_empty() {
+                    output.push_str("[]");
+                    return;
+                }
+                output.push('[');
+                for (idx, item) in items.iter().enumerate() {
+                    if idx > 0 { output.push(','); }
+                    newline(output, depth + 1);
+                    item.write(output, indent, depth + 1);
+                }
+                newline(output, depth);
+                output.push(']');
+            },
+            Json::Object(fields) => {
+                if fields.is_empty() {
+                    output.push_str("{}");
+                    return;
+                }
+                output.push('{');
+                for (idx, (key, value)) in fields.iter().enumerate() {
+                    if idx > 0 { output.push(','); }
+                    newline(output, depth + 1);
+                    write_string(output, key);
+                    output.push(':');
+                    if indent.is_some() { output.push(' '); }
+                    value.write(output, indent, depth + 1);
+                }
+                newline(output, depth);
+                output.push('}');
+            },
+        }
+    }
+}
+
+fn write_string(output: &mut String, text: &str) {
+    output.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Json::Number(value.to_string())
+    }
+}
+
+impl From<u128> for Json {
+    fn from(value: u128) -> Self {
+        Json::Number(value.to_string())
+    }
+}
+
+impl From<f64> for Json {
+    // JSON has no infinity or NaN
+    fn from(value: f64) -> Self {
+        if value.is_finite() { Json::Number(format!("{:?}", value)) } else { Json::Null }
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::String(String::from(value))
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::String(value)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Json::Null)
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(values: Vec<T>) -> Self {
+        Json::Array(values.into_iter().map(Into::into).collect())
+    }
+}
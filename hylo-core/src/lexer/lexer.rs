@@ -4,7 +4,8 @@ use crate::hylo_error;
 
 pub struct Lexer;
 impl Lexer {
-    pub fn parse(source_code: &SourceCodeContainer, file_name: &str) -> Result<Vec<TokenContainer>, hylo_error::Error> {
+    // Lexes the whole file, errors go to `diagnostics` and lexing carries on after them
+    pub fn parse(source_code: &SourceCodeContainer, file_name: &str, diagnostics: &mut hylo_error::Diagnostics) -> Vec<TokenContainer> {
         let mut generated_tokens = Vec::new();
         let mut pos:usize = 0;
 
@@ -15,6 +16,17 @@ impl Lexer {
                 continue;
             }
 
+            // Comments, only doc comments produce a token
+            if c_char == '/' && matches!(source_code.char_at(pos+1), Some('/') | Some('*')) {
+                match Self::handle_comment(source_code, &mut pos, file_name) {
+                    Ok(Some(doc_token)) => generated_tokens.push(doc_token),
+                    Ok(None) => {},
+                    // An unterminated block comment runs to the end of the file
+                    Err(e) => diagnostics.push(e)
+                }
+                continue;
+            }
+
             let generated_token_container = match c_char {
                 ';' => {
                     pos += 1;
@@ -24,6 +36,14 @@ impl Lexer {
                         end: pos - 1
                     }
                 }
+                ':' => {
+                    pos += 1;
+                    TokenContainer {
+                        token: tokens::Token::Punctuation(tokens::Punctuation::Colon),
+                        start: pos - 1,
+                        end: pos - 1
+                    }
+                }
                 ',' => {
                     pos += 1;
                     TokenContainer {
@@ -80,29 +100,52 @@ impl Lexer {
                         end: pos - 1
                     }
                 },
-                '"' => Self::handle_string(source_code, &mut pos, file_name)?,
-                '\'' => Self::handle_string(source_code, &mut pos, file_name)?,
+                '"' | '\'' => match Self::handle_string(source_code, &mut pos, file_name) {
+                    Ok(t_con) => t_con,
+                    Err(string_errors) => {
+                        // The end of the string is known, so lexing can go on after it
+                        diagnostics.extend(string_errors);
+                        continue;
+                    }
+                },
                 current_char if current_char.is_alphabetic() || current_char == '_' => {
                     Self::handle_word(source_code, &mut pos)
                 },
-                current_char if current_char.is_digit(10) => Self::handle_number(source_code, &mut pos, file_name)?,
+                current_char if current_char.is_ascii_digit() => match Self::handle_number(source_code, &mut pos, file_name) {
+                    Ok(t_con) => t_con,
+                    Err(e) => {
+                        diagnostics.push(e);
+                        // Skip the rest of the malformed literal
+                        while source_code.char_at(pos).is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.') {
+                            pos += 1;
+                        }
+                        continue;
+                    }
+                },
                 
                 // Matching operators
                 current_char if matches!(current_char, '+' | '-' | '*' | '/' | '>' |'<' | '=' | '&' | '!' | '|' | '.') => {
-                    let next_char = source_code.char_at(pos+1);
-                    let mut op_char = String::new();
-                    if let Some(next_char) = next_char  && matches!(next_char, '+' | '-' | '*' | '/' | '>' |'<' | '=' | '&' | '!' | '|' | '.') {
-                        op_char.extend([current_char, next_char].iter());
-                    }  else { op_char.push(current_char); }
+                    let mut op_char = String::from(current_char);
+                    // Two operator chars are one operator only when they are a known pair, `=-` is `=` then `-`.
+                    // A comment right after the operator is left for the next token, `+//` is `+` then a comment.
+                    let opens_comment = source_code.char_at(pos+1) == Some('/') && matches!(source_code.char_at(pos+2), Some('/') | Some('*'));
+                    if let Some(next_char) = source_code.char_at(pos+1) && !opens_comment && matches!(next_char, '+' | '-' | '*' | '/' | '>' |'<' | '=' | '&' | '!' | '|' | '.') {
+                        let pair: String = [current_char, next_char].iter().collect();
+                        if Self::handle_operators(&pair).is_some() {
+                            op_char = pair;
+                        }
+                    }
                     
                     let op_token = match Self::handle_operators(&op_char) {
                         Some(t) => t,
                         None => {
-                            return Err(hylo_error::Error::new(
+                            diagnostics.push(hylo_error::Error::new(
                                 hylo_error::ErrorKind::SyntaxError,
                                 hylo_error::Span { start: pos.clone(), stop: pos.clone() + op_char.len() - 1 },
                                 Some(file_name)
                             ).add_msg("The operator is invalid"));
+                            pos += op_char.len();
+                            continue;
                         }
                     };
                     
@@ -115,18 +158,19 @@ impl Lexer {
                     
                 }
                 _ => {
-                    return Err(hylo_error::Error::new(
+                    diagnostics.push(hylo_error::Error::new(
                         hylo_error::ErrorKind::SyntaxError,
                         hylo_error::Span { start: pos.clone(), stop: pos.clone() },
                         Some(file_name)
                     ).add_msg("The token is invalid"));
-
+                    pos += 1;
+                    continue;
                 }
             };
             generated_tokens.push(generated_token_container);
         }
 
-        return Ok(generated_tokens);
+        return generated_tokens;
     }
     
     fn handle_operators(op_char: &str) -> Option<tokens::Token> {
@@ -156,23 +200,99 @@ impl Lexer {
         return Some(op_token)
     }
     
-    fn handle_string(source_code: &SourceCodeContainer, pos: &mut usize, file_name: &str) -> Result<TokenContainer, hylo_error::Error> {
+    /*
+     *  Handles every comment starting at `pos`:
+     *  - `// ...`   line comment
+     *  - `/* ... */` block comment, may be nested
+     *  - `/// ...`  outer doc comment, documents the following declaration
+     *  - `//! ...`  inner doc comment, documents the enclosing file
+     */
+    fn handle_comment(source_code: &SourceCodeContainer, pos: &mut usize, file_name: &str) -> Result<Option<TokenContainer>, hylo_error::Error> {
+        let start_pos = pos.clone();
+
+        if source_code.char_at(start_pos+1) == Some('*') {
+            *pos += 2;
+            let mut depth: usize = 1;
+            while *pos < source_code.total_chars {
+                let c_char = source_code.char_at(*pos).unwrap();
+                let next_char = source_code.char_at(*pos+1);
+                if c_char == '/' && next_char == Some('*') {
+                    depth += 1;
+                    *pos += 2;
+                } else if c_char == '*' && next_char == Some('/') {
+                    depth -= 1;
+                    *pos += 2;
+                    if depth == 0 {
+                        return Ok(None);
+                    }
+                } else { *pos += 1; }
+            }
+
+            // UnterminatedBlockComment Error
+            return Err(hylo_error::Error::new(
+                hylo_error::ErrorKind::UnterminatedBlockComment,
+                hylo_error::Span { start: start_pos, stop: start_pos + 1 },
+                Some(file_name)
+            ).add_msg("Block comment is never closed")
+            .add_note("Add a matching '*/' to close the comment"));
+        }
+
+        // `///` is a doc comment but `////` is a regular one
+        let doc_style = match (source_code.char_at(start_pos+2), source_code.char_at(start_pos+3)) {
+            (Some('/'), next_char) if next_char != Some('/') => Some(tokens::DocStyle::Outer),
+            (Some('!'), _) => Some(tokens::DocStyle::Inner),
+            _ => None
+        };
+
+        while *pos < source_code.total_chars && source_code.char_at(*pos).unwrap() != '\n' {
+            *pos += 1;
+        }
+
+        let doc_style = match doc_style {
+            Some(doc_style) => doc_style,
+            None => return Ok(None)
+        };
+        let text = if *pos > start_pos + 3 {
+            source_code.get_text(start_pos+3, pos.clone()-1).unwrap()
+        } else { String::new() };
+
+        return Ok(Some(TokenContainer {
+            token: tokens::Token::DocComment(doc_style, text),
+            start: start_pos,
+            end: pos.clone()-1
+        }));
+    }
+
+    fn handle_string(source_code: &SourceCodeContainer, pos: &mut usize, file_name: &str) -> Result<TokenContainer, Vec<hylo_error::Error>> {
         let start_pos = pos.clone();
         let start_sym = source_code.char_at(*pos).unwrap();
         *pos += 1;
 
         let mut string_terminated = false;
         let mut first_line_end_pos: i32 = -1;
+        let mut string_data = String::new();
+        let mut errors = Vec::new();
 
         while *pos < source_code.total_chars {
-            if source_code.char_at(*pos).unwrap() == start_sym {
+            let c_char = source_code.char_at(*pos).unwrap();
+            if c_char == start_sym {
                 string_terminated = true;
                 break;
             }
 
-            if first_line_end_pos == -1 && source_code.char_at(*pos).unwrap() == '\n' {
+            if c_char == '\\' {
+                // Bad escapes are collected so every one of them gets reported
+                match Self::handle_escape(source_code, pos, file_name) {
+                    Ok(escaped_char) => string_data.push(escaped_char),
+                    Err(e) => errors.push(e)
+                }
+                continue;
+            }
+
+            if first_line_end_pos == -1 && c_char == '\n' {
                 first_line_end_pos = pos.clone() as i32
             }
+            string_data.push(c_char);
             *pos += 1;
         }
         
@@ -182,15 +302,19 @@ impl Lexer {
             }
 
             // StringNotTerminated Error
-            return Err(hylo_error::Error::new(
+            errors.push(hylo_error::Error::new(
                 hylo_error::ErrorKind::StringNotTerminated,
                 hylo_error::Span { start: start_pos, stop: first_line_end_pos as usize },
                 Some(file_name)
             ).add_msg("Expected end of the string"));
+            return Err(errors);
+        }
 
+        if !errors.is_empty() {
+            *pos += 1;
+            return Err(errors);
         }
 
-        let string_data = source_code.get_text(start_pos+1, pos.clone()-1).unwrap();
         let t_container = TokenContainer {
             token: tokens::Token::String(string_data),
             start: start_pos,
@@ -200,6 +324,103 @@ impl Lexer {
         *pos += 1;
         return Ok(t_container);
     }
+
+    /*
+     *  Handles the escape sequence starting at the '\' under `pos`:
+     *  \n \t \r \\ \" \' \0 \xNN \u{XXXX}
+     *  On error the span covers only the escape sequence.
+     */
+    fn handle_escape(source_code: &SourceCodeContainer, pos: &mut usize, file_name: &str) -> Result<char, hylo_error::Error> {
+        let start_pos = pos.clone();
+        *pos += 1;
+        let escape_char = match source_code.char_at(*pos) {
+            Some(c) => c,
+            None => {
+                return Err(hylo_error::Error::new(
+                    hylo_error::ErrorKind::InvalidEscape,
+                    hylo_error::Span { start: start_pos, stop: start_pos },
+                    Some(file_name)
+                ).add_msg("Expected an escape sequence after '\\'"));
+            }
+        };
+        *pos += 1;
+
+        let invalid_escape = |pos: usize, msg: &str| {
+            hylo_error::Error::new(
+                hylo_error::ErrorKind::InvalidEscape,
+                hylo_error::Span { start: start_pos, stop: pos - 1 },
+                Some(file_name)
+            ).add_msg(msg)
+        };
+
+        match escape_char {
+            'n' => return Ok('\n'),
+            't' => return Ok('\t'),
+            'r' => return Ok('\r'),
+            '\\' => return Ok('\\'),
+            '"' => return Ok('"'),
+            '\'' => return Ok('\''),
+            '0' => return Ok('\0'),
+            'x' => {
+                let mut value: u32 = 0;
+                for _ in 0..2 {
+                    match source_code.char_at(*pos).and_then(|c| c.to_digit(16)) {
+                        Some(digit) => {
+                            value = value * 16 + digit;
+                            *pos += 1;
+                        },
+                        None => {
+                            return Err(invalid_escape(*pos, "Expected two hex digits after '\\x'")
+                                .add_note("Write ASCII escapes as '\\x' followed by two hex digits, e.g. '\\x41'"));
+                        }
+                    }
+                }
+
+                if value > 0x7F {
+                    return Err(invalid_escape(*pos, "'\\x' escapes must be in the range 0x00 to 0x7F")
+                        .add_note(&format!("Use a unicode escape for non-ASCII characters: '\\u{{{:X}}}'", value)));
+                }
+                return Ok(char::from_u32(value).unwrap());
+            },
+            'u' => {
+                if source_code.char_at(*pos) != Some('{') {
+                    return Err(invalid_escape(*pos, "Expected '{' after '\\u'")
+                        .add_note("Write unicode escapes as '\\u{XXXX}' with 1 to 6 hex digits"));
+                }
+                *pos += 1;
+
+                let mut value: u32 = 0;
+                let mut digit_count = 0;
+                while let Some(digit) = source_code.char_at(*pos).and_then(|c| c.to_digit(16)) {
+                    // Saturates instead of overflowing, the digit count check below reports it
+                    value = value.saturating_mul(16).saturating_add(digit);
+                    digit_count += 1;
+                    *pos += 1;
+                }
+
+                if source_code.char_at(*pos) != Some('}') {
+                    return Err(invalid_escape(*pos, "Expected '}' to close the unicode escape")
+                        .add_note("Write unicode escapes as '\\u{XXXX}' with 1 to 6 hex digits"));
+                }
+                *pos += 1;
+
+                if digit_count == 0 {
+                    return Err(invalid_escape(*pos, "Unicode escape must have at least one hex digit"));
+                } else if digit_count > 6 {
+                    return Err(invalid_escape(*pos, "Unicode escape must have at most 6 hex digits"));
+                }
+                return match char::from_u32(value) {
+                    Some(c) => Ok(c),
+                    None => Err(invalid_escape(*pos, &format!("'{:X}' is not a valid unicode character", value))
+                        .add_note("Unicode escapes must be at most 10FFFF and outside the surrogate range D800 to DFFF"))
+                };
+            },
+            _ => {
+                return Err(invalid_escape(*pos, &format!("Unknown escape sequence '\\{}'", escape_char))
+                    .add_note("Valid escapes are \\n \\t \\r \\\\ \\\" \\' \\0 \\xNN and \\u{XXXX}"));
+            }
+        }
+    }
     
 
     fn handle_word(source_code: &SourceCodeContainer, pos: &mut usize) -> TokenContainer {
@@ -216,6 +437,18 @@ impl Lexer {
         let token = match word.as_str() {
             "true" => tokens::Token::Boolean(true),
             "false" => tokens::Token::Boolean(false),
+            
+            "let" => tokens::Token::Keyword(tokens::Keyword::Let),
+            "const" => tokens::Token::Keyword(tokens::Keyword::Const),
+            "fn" => tokens::Token::Keyword(tokens::Keyword::Fn),
+            "return" => tokens::Token::Keyword(tokens::Keyword::Return),
+            "if" => tokens::Token::Keyword(tokens::Keyword::If),
+            "else" => tokens::Token::Keyword(tokens::Keyword::Else),
+            "while" => tokens::Token::Keyword(tokens::Keyword::While),
+            "loop" => tokens::Token::Keyword(tokens::Keyword::Loop),
+            "break" => tokens::Token::Keyword(tokens::Keyword::Break),
+            "continue" => tokens::Token::Keyword(tokens::Keyword::Continue),
+            
             _ => tokens::Token::Word(word)
         };
 
@@ -226,55 +459,183 @@ impl Lexer {
         }
     }
 
+    /*
+     *  Number literals:
+     *  - decimal      1_000, 1.5, 1e-9, 2.5E+3
+     *  - hex          0xFF
+     *  - octal        0o777
+     *  - binary       0b1010_1010
+     *  each optionally followed by a type suffix: 10i64, 2.5f64, 0xFFu8
+     */
     fn handle_number(source_code: &SourceCodeContainer, pos: &mut usize, file_name: &str) -> Result<TokenContainer, hylo_error::Error> {
         let start_pos = pos.clone();
+        let syntax_error = |start: usize, stop: usize, msg: &str| {
+            hylo_error::Error::new(
+                hylo_error::ErrorKind::SyntaxError,
+                hylo_error::Span { start, stop },
+                Some(file_name)
+            ).add_msg(msg)
+        };
 
-        let mut is_float = false;
-        while *pos < source_code.total_chars {
-            let c_char = source_code.char_at(*pos).unwrap();
-            if !(c_char.is_digit(10) || c_char == '.') {
-                break;
-            } else if c_char == '.' { is_float = true; }
+        // Radix prefix
+        let radix = match (source_code.char_at(*pos), source_code.char_at(*pos+1)) {
+            (Some('0'), Some('x')) => 16,
+            (Some('0'), Some('o')) => 8,
+            (Some('0'), Some('b')) => 2,
+            _ => 10
+        };
+        if radix != 10 {
+            *pos += 2;
+        }
+        let radix_name = match radix {
+            16 => "hexadecimal",
+            8 => "octal",
+            2 => "binary",
+            _ => "decimal"
+        };
 
+        // Integer part. Decimal digits are accepted for every radix so that
+        // a digit like the `2` in `0b102` gets its own error.
+        let mut digits = String::new();
+        while let Some(c_char) = source_code.char_at(*pos) {
+            if c_char == '_' {
+                *pos += 1;
+                continue;
+            }
+            let is_digit = if radix == 16 { c_char.is_ascii_hexdigit() } else { c_char.is_ascii_digit() };
+            if !is_digit { break }
+
+            if c_char.to_digit(radix).is_none() {
+                return Err(syntax_error(*pos, *pos, &format!("Invalid digit '{}' in a {} literal", c_char, radix_name)));
+            }
+            digits.push(c_char);
             *pos += 1;
         }
 
-        let number_str = source_code.get_text(start_pos, pos.clone()-1).unwrap();
-        if is_float {
-            match number_str.parse::<f32>() {
-                Ok(value) => {
-                    return Ok(TokenContainer { token: tokens::Token::Float(value),
-                        start: start_pos,
-                        end: pos.clone()-1
-                    })
+        if digits.is_empty() {
+            return Err(syntax_error(start_pos, *pos - 1, &format!("Expected at least one digit in the {} literal", radix_name)));
+        }
+
+        let mut is_float = false;
+        if radix == 10 {
+            // Fraction, only when a digit follows the '.' so `1.method()` stays a member access
+            if source_code.char_at(*pos) == Some('.') && source_code.char_at(*pos+1).is_some_and(|c| c.is_ascii_digit()) {
+                is_float = true;
+                digits.push('.');
+                *pos += 1;
+                while let Some(c_char) = source_code.char_at(*pos) {
+                    if c_char.is_ascii_digit() {
+                        digits.push(c_char);
+                    } else if c_char != '_' { break }
+                    *pos += 1;
                 }
-                Err(_) => {
-                    // InvalidNumber Error for float
-                    return Err(hylo_error::Error::new(
-                        hylo_error::ErrorKind::SyntaxError,
-                        hylo_error::Span { start: start_pos, stop: pos.clone() - 1 },
-                        Some(file_name)
-                    ).add_msg("The float is not valid"));
+
+                if source_code.char_at(*pos) == Some('.') && source_code.char_at(*pos+1).is_some_and(|c| c.is_ascii_digit()) {
+                    return Err(syntax_error(*pos, *pos, "A number literal can only have one decimal point"));
+                }
+            }
+
+            // Exponent
+            if matches!(source_code.char_at(*pos), Some('e') | Some('E'))
+                && source_code.char_at(*pos+1).is_some_and(|c| c.is_ascii_digit() || matches!(c, '_' | '+' | '-')) {
+                is_float = true;
+                let exponent_start = pos.clone();
+                digits.push('e');
+                *pos += 1;
+                if let Some(sign) = source_code.char_at(*pos) && matches!(sign, '+' | '-') {
+                    digits.push(sign);
+                    *pos += 1;
+                }
+
+                let mut has_digits = false;
+                while let Some(c_char) = source_code.char_at(*pos) {
+                    if c_char.is_ascii_digit() {
+                        digits.push(c_char);
+                        has_digits = true;
+                    } else if c_char != '_' { break }
+                    *pos += 1;
+                }
+                if !has_digits {
+                    return Err(syntax_error(exponent_start, *pos - 1, "Expected at least one digit in the exponent"));
                 }
             }
-        } else {
-            match number_str.parse::<i32>() {
+        }
+
+        // Suffix
+        let suffix_start = pos.clone();
+        while let Some(c_char) = source_code.char_at(*pos) {
+            if !c_char.is_alphanumeric() && c_char != '_' { break }
+            *pos += 1;
+        }
+        let suffix = if *pos > suffix_start {
+            let suffix_str = source_code.get_text(suffix_start, *pos - 1).unwrap();
+            let suffix = match Self::handle_number_suffix(&suffix_str) {
+                Some(suffix) => suffix,
+                None => {
+                    return Err(syntax_error(suffix_start, *pos - 1, &format!("Invalid suffix '{}' for a number literal", suffix_str))
+                        .add_note("Valid suffixes are i8, i16, i32, i64, u8, u16, u32, u64, f32 and f64"));
+                }
+            };
+
+            if is_float && !suffix.is_float() {
+                return Err(syntax_error(suffix_start, *pos - 1, &format!("Float literal can't have the integer suffix '{}'", suffix_str))
+                    .add_note("Use f32 or f64 for float literals"));
+            } else if radix != 10 && suffix.is_float() {
+                return Err(syntax_error(suffix_start, *pos - 1, &format!("A {} literal can't have the float suffix '{}'", radix_name, suffix_str)));
+            }
+            Some(suffix)
+        } else { None };
+
+        let text = source_code.get_text(start_pos, pos.clone() - 1).unwrap();
+        if is_float || suffix.is_some_and(|suffix| suffix.is_float()) {
+            match digits.parse::<f64>() {
                 Ok(value) => {
-                    return Ok(TokenContainer { token: tokens::Token::Int(value),
+                    return Ok(TokenContainer { token: tokens::Token::Float(tokens::FloatLiteral { text, value, suffix }),
                         start: start_pos,
                         end: pos.clone()-1
                     })
                 }
                 Err(_) => {
-                    // InvalidNumber Error for integer
-                    return Err(hylo_error::Error::new(
-                        hylo_error::ErrorKind::SyntaxError,
-                        hylo_error::Span { start: start_pos, stop: pos.clone() - 1 },
-                        Some(file_name)
-                    ).add_msg("The integer is not valid"));
+                    // InvalidNumber Error for float
+                    return Err(syntax_error(start_pos, pos.clone() - 1, "The float is not valid"));
                 }
             }
         }
+
+        match u128::from_str_radix(&digits, radix) {
+            Ok(value) => {
+                return Ok(TokenContainer { token: tokens::Token::Int(tokens::IntLiteral { text, value, suffix }),
+                    start: start_pos,
+                    end: pos.clone()-1
+                })
+            }
+            Err(_) => {
+                return Err(hylo_error::Error::new(
+                    hylo_error::ErrorKind::IntegerLiteralTooLarge,
+                    hylo_error::Span { start: start_pos, stop: pos.clone() - 1 },
+                    Some(file_name)
+                ).add_msg("Integer literal is too large")
+                .add_note(&format!("Integer literals can be at most {}", u128::MAX)));
+            }
+        }
+    }
+
+    fn handle_number_suffix(suffix: &str) -> Option<tokens::NumberSuffix> {
+        let suffix = match suffix {
+            "i8" => tokens::NumberSuffix::I8,
+            "i16" => tokens::NumberSuffix::I16,
+            "i32" => tokens::NumberSuffix::I32,
+            "i64" => tokens::NumberSuffix::I64,
+            "u8" => tokens::NumberSuffix::U8,
+            "u16" => tokens::NumberSuffix::U16,
+            "u32" => tokens::NumberSuffix::U32,
+            "u64" => tokens::NumberSuffix::U64,
+            "f32" => tokens::NumberSuffix::F32,
+            "f64" => tokens::NumberSuffix::F64,
+            _ => return None
+        };
+
+        return Some(suffix)
     }
 }
 
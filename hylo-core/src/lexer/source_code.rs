@@ -1,118 +1,103 @@
-#[derive(Debug)]
-pub struct Line {
-    pub line: Vec<char>,
-    pub start_pos: usize,
-    pub end_pos: usize,
-}
-impl Line {
-    pub fn get_relative_pos(&self, pos: usize) -> usize {
-        return pos - self.start_pos;
-    }
-
-    pub fn get_char_relative(&self, pos: usize) -> char {
-        let rel_pos = self.get_relative_pos(pos);
-        return self.line[rel_pos];
-    }
-}
-
+/*
+ * SOURCE CODE
+ *
+ * The chars of a file in one buffer, with the position every line starts
+ * at. Positions are char indices. A char is found by indexing the buffer,
+ * its line by a binary search over the line starts, so the lexer stays
+ * linear in the size of the file.
+ *
+ * Every line ends with a '\n', the last one included even when the file
+ * doesn't, so the final position of the buffer is always a newline.
+ */
 #[derive(Debug)]
 pub struct SourceCodeContainer {
-    pub source_code: Vec<Line>,
+    chars: Vec<char>,
+    // Position of the first char of each line
+    line_starts: Vec<usize>,
+    // UTF-8 offset of the first char of each line
+    line_byte_starts: Vec<usize>,
     pub total_lines: usize,
     pub total_chars: usize,
 }
 
 impl SourceCodeContainer {
     pub fn from(source_code: String) -> Self {
-        let mut code_container = Self {
-            source_code: Vec::new(),
-            total_lines: 0,
-            total_chars: 0,
-        };
-
-        let mut pos_count: usize = 0;
-        for l in source_code.split("\n") {
-            let line_chars = {
-                let mut chars: Vec<char> = l.chars().collect();
-                chars.push('\n');
-                chars
-            };
-            let end_pos = pos_count + line_chars.len() - 1;
-
-            let s_line = Line {
-                line: line_chars,
-                start_pos: pos_count,
-                end_pos: end_pos,
-            };
-            pos_count = s_line.end_pos + 1;
-
-            code_container.source_code.push(s_line);
-            code_container.total_lines += 1;
+        let mut chars: Vec<char> = Vec::with_capacity(source_code.len() + 1);
+        let mut line_starts = vec![0];
+        let mut line_byte_starts = vec![0];
+
+        for (byte_pos, c) in source_code.char_indices() {
+            chars.push(c);
+            if c == '\n' {
+                line_starts.push(chars.len());
+                line_byte_starts.push(byte_pos + 1);
+            }
         }
-        code_container.total_chars = pos_count;
-
-        return code_container;
+        chars.push('\n');
+
+        return Self {
+            total_lines: line_starts.len(),
+            total_chars: chars.len(),
+            chars: chars,
+            line_starts: line_starts,
+            line_byte_starts: line_byte_starts,
+        };
     }
 
     pub fn char_at(&self, pos: usize) -> Option<char> {
-        if pos >= self.total_chars {
-            return None;
-        }
-
-        for l in self.source_code.iter() {
-            if pos > l.end_pos {
-                continue;
-            }
+        return self.chars.get(pos).cloned();
+    }
 
-            return Some(l.get_char_relative(pos));
+    // The chars from `start_pos` to `end_pos`, both included
+    pub fn get_text(&self, start_pos: usize, end_pos: usize) -> Option<String> {
+        if start_pos > end_pos || end_pos >= self.total_chars {
+            return None;
         }
 
-        return None;
+        return Some(self.chars[start_pos..=end_pos].iter().collect());
     }
 
-    pub fn get_text(&self, start_pos: usize, end_pos: usize) -> Option<String> {
-        if start_pos > end_pos || end_pos > self.total_chars - 1 {
+    // Zero based index of the line `pos` is on, None past the end
+    pub fn line_index(&self, pos: usize) -> Option<usize> {
+        if pos >= self.total_chars {
             return None;
         }
 
-        let mut text_data = String::new();
-        for (idx, l) in self.source_code.iter().enumerate() {
-            if start_pos > l.end_pos {
-                continue;
-            }
-
-            let mut c_pos = start_pos;
-            let mut vec_idx = idx;
-            while c_pos <= end_pos {
-                let rel_pos = self.source_code[vec_idx].get_relative_pos(c_pos);
-                text_data.push(self.source_code[vec_idx].line[rel_pos]);
+        // The last line starting at or before `pos`
+        return Some(self.line_starts.partition_point(|&start| start <= pos) - 1);
+    }
 
-                if c_pos == self.source_code[vec_idx].end_pos {
-                    vec_idx += 1;
-                }
+    // Position of the first char of a line and of its '\n'
+    pub fn line_span(&self, line_idx: usize) -> (usize, usize) {
+        let start_pos = self.line_starts[line_idx];
+        let end_pos = match self.line_starts.get(line_idx + 1) {
+            Some(next_start) => next_start - 1,
+            None => self.total_chars - 1,
+        };
+        return (start_pos, end_pos);
+    }
 
-                c_pos += 1;
-            }
+    // The chars of a line, without its '\n'
+    pub fn line(&self, line_idx: usize) -> &[char] {
+        let (start_pos, end_pos) = self.line_span(line_idx);
+        return &self.chars[start_pos..end_pos];
+    }
 
-            break;
-        }
+    // UTF-8 offset of the char at `pos`
+    pub fn byte_offset(&self, pos: usize) -> usize {
+        let pos = pos.min(self.total_chars);
+        let line_idx = self.line_starts.partition_point(|&start| start <= pos) - 1;
+        let line_start = self.line_starts[line_idx];
 
-        return Some(text_data);
+        let in_line: usize = self.chars[line_start..pos].iter().map(|c| c.len_utf8()).sum();
+        return self.line_byte_starts[line_idx] + in_line;
     }
 
+    // One based line and column of `pos`, the column is 0 past the end
     pub fn get_line_and_column(&self, pos: usize) -> (usize, usize) {
-        let mut lineno: usize = 0;
-        let mut columnno: usize = 0;
-        for l in self.source_code.iter() {
-            lineno += 1;
-            if pos > l.end_pos {
-                continue;
-            }
-
-            columnno = l.get_relative_pos(pos) + 1;
-            break;
+        match self.line_index(pos) {
+            Some(line_idx) => return (line_idx + 1, pos - self.line_starts[line_idx] + 1),
+            None => return (self.total_lines, 0),
         }
-
-        return (lineno, columnno);
     }
 }
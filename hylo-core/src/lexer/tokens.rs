@@ -1,16 +1,27 @@
 // Represents all possible tokens in Hylo
 #[derive(Debug, PartialEq, Clone)]
 pub enum Token {
-    Int(i32),
-    Float(f32),
+    Int(IntLiteral),
+    Float(FloatLiteral),
     String(String),
     Boolean(bool),
     Word(String),
+    DocComment(DocStyle, String),
 
+    Keyword(Keyword),
     Operator(Operator),
     Punctuation(Punctuation)
 }
 
+/// Which item a doc comment documents.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DocStyle {
+    /// `///`, documents the following declaration
+    Outer,
+    /// `//!`, documents the enclosing file
+    Inner
+}
+
 /*
 Complex Tokens:
     - Keyword
@@ -18,6 +29,120 @@ Complex Tokens:
     - Punctuation
 */
 
+/// Represents an integer literal. The value is stored in the widest form,
+/// later stages decide its type and check that it fits.
+#[derive(Debug, PartialEq, Clone)]
+pub struct IntLiteral {
+    /// The literal as written in the source, e.g. `0xFF_u8`
+    pub text: String,
+    pub value: u128,
+    pub suffix: Option<NumberSuffix>
+}
+
+/// Represents a float literal, see `IntLiteral`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FloatLiteral {
+    /// The literal as written in the source, e.g. `2.5e3f32`
+    pub text: String,
+    pub value: f64,
+    pub suffix: Option<NumberSuffix>
+}
+
+/// Represents the type suffix of a number literal, e.g. `10i64`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum NumberSuffix {
+    I8,
+    I16,
+    I32,
+    I64,
+
+    U8,
+    U16,
+    U32,
+    U64,
+
+    F32,
+    F64
+}
+
+impl NumberSuffix {
+    pub fn as_symbol(&self) -> &'static str {
+        match self {
+            NumberSuffix::I8  => "i8",
+            NumberSuffix::I16 => "i16",
+            NumberSuffix::I32 => "i32",
+            NumberSuffix::I64 => "i64",
+
+            NumberSuffix::U8  => "u8",
+            NumberSuffix::U16 => "u16",
+            NumberSuffix::U32 => "u32",
+            NumberSuffix::U64 => "u64",
+
+            NumberSuffix::F32 => "f32",
+            NumberSuffix::F64 => "f64"
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, NumberSuffix::F32 | NumberSuffix::F64)
+    }
+
+    /// Largest value an integer of this type can hold, `None` for floats.
+    pub fn int_max(&self) -> Option<u128> {
+        match self {
+            NumberSuffix::I8  => Some(i8::MAX as u128),
+            NumberSuffix::I16 => Some(i16::MAX as u128),
+            NumberSuffix::I32 => Some(i32::MAX as u128),
+            NumberSuffix::I64 => Some(i64::MAX as u128),
+
+            NumberSuffix::U8  => Some(u8::MAX as u128),
+            NumberSuffix::U16 => Some(u16::MAX as u128),
+            NumberSuffix::U32 => Some(u32::MAX as u128),
+            NumberSuffix::U64 => Some(u64::MAX as u128),
+
+            NumberSuffix::F32 | NumberSuffix::F64 => None
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        matches!(self, NumberSuffix::I8 | NumberSuffix::I16 | NumberSuffix::I32 | NumberSuffix::I64)
+    }
+}
+
+/// Represents reserved keywords in Hylo.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Keyword {
+    Let,
+    Const,
+    Fn,
+    Return,
+
+    If,
+    Else,
+    While,
+    Loop,
+    Break,
+    Continue
+}
+
+impl Keyword {
+    pub fn as_symbol(&self) -> &'static str {
+        match self {
+            Keyword::Let    => "let",
+            Keyword::Const  => "const",
+            Keyword::Fn     => "fn",
+            Keyword::Return => "return",
+
+            Keyword::If       => "if",
+            Keyword::Else     => "else",
+            Keyword::While    => "while",
+            Keyword::Loop     => "loop",
+            Keyword::Break    => "break",
+            Keyword::Continue => "continue"
+        }
+    }
+}
+
 /// Represents operators in Hylo.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Operator {
@@ -73,6 +198,7 @@ impl Operator {
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Punctuation {
     Semicolon,
+    Colon,
     Comma,
     LParen, 
     RParen,
@@ -86,6 +212,7 @@ impl Punctuation {
     pub fn as_symbol(&self) -> &'static str {
         match self {
             Punctuation::Semicolon => ";",
+            Punctuation::Colon => ":",
             Punctuation::Comma => ",",
             Punctuation::LParen    => "(",
             Punctuation::RParen    => ")",
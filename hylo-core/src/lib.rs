@@ -1,4 +1,9 @@
 pub mod compiler;
 pub mod lexer;
 pub mod parser;
+pub mod cst;
+pub mod formatter;
+pub mod emit;
+pub mod json;
+pub mod lint;
 pub mod hylo_error;
\ No newline at end of file
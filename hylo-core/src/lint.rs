@@ -0,0 +1,124 @@
+/*
+ * LINT LEVELS
+ *
+ * Decides what happens to each warning: it is dropped (allow), reported
+ * (warn) or turned into an error (deny). The command line sets the levels
+ * for a whole run, a pragma comment allows a warning on a single line:
+ *
+ *     // hylo: allow(W0002)
+ *     while true { ... }
+ *
+ * A pragma on its own line covers the next line of code, a pragma after
+ * code covers the line it is on. Pragmas win over the command line.
+ */
+use crate::cst::{SyntaxTokens, TriviaKind};
+use crate::hylo_error::{self, ErrorKind, Severity};
+use crate::lexer::lexer::TokenContainer;
+use crate::lexer::source_code::SourceCodeContainer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintLevels {
+    // Later entries win, so `-A W0001 -W W0001` warns
+    levels: Vec<(ErrorKind, LintLevel)>,
+    // Denies the warnings that weren't given a level
+    deny_warnings: bool,
+}
+
+impl LintLevels {
+    pub fn new() -> Self {
+        Self { levels: Vec::new(), deny_warnings: false }
+    }
+
+    pub fn set(&mut self, kind: ErrorKind, level: LintLevel) {
+        self.levels.push((kind, level));
+    }
+
+    pub fn set_deny_warnings(&mut self, deny_warnings: bool) {
+        self.deny_warnings = deny_warnings;
+    }
+
+    pub fn level(&self, kind: &ErrorKind) -> LintLevel {
+        let explicit = self.levels.iter().rev().find(|(k, _)| k == kind).map(|(_, level)| *level);
+        match explicit {
+            Some(level) => level,
+            None if self.deny_warnings => LintLevel::Deny,
+            None => LintLevel::Warn,
+        }
+    }
+
+    // Drops the allowed warnings and turns the denied ones into errors
+    pub fn apply(&self, diagnostics: hylo_error::Diagnostics, pragmas: &Pragmas) -> hylo_error::Diagnostics {
+        let mut result = hylo_error::Diagnostics::new();
+        for e in diagnostics.into_errors() {
+            if !e.kind.is_warning() {
+                result.push(e);
+                continue;
+            }
+            if pragmas.allows(&e.kind, e.span.start) {
+                continue;
+            }
+
+            match self.level(&e.kind) {
+                LintLevel::Allow => {},
+                LintLevel::Warn => result.push(e),
+                LintLevel::Deny => {
+                    let note = format!("`{}` is denied, so this warning is an error", e.kind.code());
+                    result.push(e.with_severity(Severity::Error).add_note(&note));
+                },
+            }
+        }
+        return result;
+    }
+}
+
+// The warnings allowed by `// hylo: allow(...)` comments, per line of code
+#[derive(Debug, Clone, Default)]
+pub struct Pragmas {
+    // Warning and the first and last char of the line it is allowed on
+    allowed: Vec<(ErrorKind, usize, usize)>,
+}
+
+impl Pragmas {
+    pub fn collect(source_code: &SourceCodeContainer, token_containers: &[TokenContainer]) -> Self {
+        let mut pragmas = Self::default();
+        let syntax_tokens = SyntaxTokens::from(source_code, token_containers);
+
+        for token in &syntax_tokens.tokens {
+            let comments = token.leading_trivia.iter()
+                .chain(token.trailing_trivia.iter())
+                .filter(|trivia| trivia.kind == TriviaKind::LineComment);
+
+            for comment in comments {
+                let Some(kinds) = parse_pragma(&comment.text) else { continue };
+                // Leading trivia ends on the token's line and trailing trivia starts on it
+                let (line_no, _) = source_code.get_line_and_column(token.start);
+                let (start_pos, end_pos) = source_code.line_span(line_no - 1);
+                for kind in kinds {
+                    pragmas.allowed.push((kind, start_pos, end_pos));
+                }
+            }
+        }
+
+        return pragmas;
+    }
+
+    pub fn allows(&self, kind: &ErrorKind, pos: usize) -> bool {
+        return self.allowed.iter().any(|(k, start, end)| k == kind && *start <= pos && pos <= *end);
+    }
+}
+
+// `// hylo: allow(W0001, while_true)`, unknown warnings are ignored
+fn parse_pragma(comment: &str) -> Option<Vec<ErrorKind>> {
+    let text = comment.strip_prefix("//")?.trim();
+    let text = text.strip_prefix("hylo:")?.trim();
+    let list = text.strip_prefix("allow(")?.strip_suffix(')')?;
+
+    return Some(list.split(',').filter_map(|name| ErrorKind::warning_from(name.trim())).collect());
+}
@@ -0,0 +1,276 @@
+use crate::parser::components::{Block, ElseBody, Expr, Literal, Program, Span, Stmt, UnaryOp};
+use crate::lexer::tokens::NumberSuffix;
+use crate::hylo_error;
+
+/*
+ * Checks that run on the parsed program and reject code that
+ * is syntactically fine but misplaced, or warn about code that
+ * is valid but likely not what was meant.
+ */
+
+// Rejects `break` and `continue` that are not inside a `while` or `loop`
+pub fn check_loop_control(program: &Program, file_name: &str) -> Vec<hylo_error::Error> {
+    let mut errors = Vec::new();
+    for stmt in &program.statements {
+        check_stmt(stmt, false, file_name, &mut errors);
+    }
+    return errors;
+}
+
+fn check_block(block: &Block, in_loop: bool, file_name: &str, errors: &mut Vec<hylo_error::Error>) {
+    for stmt in &block.statements {
+        check_stmt(stmt, in_loop, file_name, errors);
+    }
+}
+
+fn check_stmt(stmt: &Stmt, in_loop: bool, file_name: &str, errors: &mut Vec<hylo_error::Error>) {
+    match stmt {
+        Stmt::Block(block) => check_block(block, in_loop, file_name, errors),
+        Stmt::If { then_block, else_branch, .. } => {
+            check_block(then_block, in_loop, file_name, errors);
+            if let Some(else_branch) = else_branch {
+                match &else_branch.body {
+                    ElseBody::Block(block) => check_block(block, in_loop, file_name, errors),
+                    ElseBody::If(stmt) => check_stmt(stmt, in_loop, file_name, errors),
+                }
+            }
+        },
+        Stmt::While { body, .. } | Stmt::Loop { body, .. } => check_block(body, true, file_name, errors),
+        // A function body starts outside of any loop, even when declared inside one
+        Stmt::FnDecl(fn_decl) => check_block(&fn_decl.body, false, file_name, errors),
+        Stmt::Break { keyword, .. } if !in_loop => {
+            errors.push(hylo_error::Error::new(
+                hylo_error::ErrorKind::LoopControlOutsideLoop,
+                (*keyword).into(),
+                Some(file_name)
+            ).add_msg("`break` outside of a loop")
+            .add_note("`break` can only be used inside `while` or `loop`"));
+        },
+        Stmt::Continue { keyword, .. } if !in_loop => {
+            errors.push(hylo_error::Error::new(
+                hylo_error::ErrorKind::LoopControlOutsideLoop,
+                (*keyword).into(),
+                Some(file_name)
+            ).add_msg("`continue` outside of a loop")
+            .add_note("`continue` can only be used inside `while` or `loop`"));
+        },
+        _ => {}
+    }
+}
+
+// Calls `on_expr` with every top-level expression of `stmt` and its nested statements
+fn walk_stmt<'a>(stmt: &'a Stmt, on_expr: &mut dyn FnMut(&'a Expr)) {
+    let walk_block = |block: &'a Block, on_expr: &mut dyn FnMut(&'a Expr)| {
+        for stmt in &block.statements {
+            walk_stmt(stmt, on_expr);
+        }
+    };
+
+    match stmt {
+        Stmt::ExprStmt { expr, .. } => on_expr(expr),
+        Stmt::Let { init, .. } => on_expr(init),
+        Stmt::Assign { target, value, .. } => {
+            on_expr(target);
+            on_expr(value);
+        },
+        Stmt::Block(block) => walk_block(block, on_expr),
+        Stmt::If { condition, then_block, else_branch, .. } => {
+            on_expr(condition);
+            walk_block(then_block, on_expr);
+            if let Some(else_branch) = else_branch {
+                match &else_branch.body {
+                    ElseBody::Block(block) => walk_block(block, on_expr),
+                    ElseBody::If(stmt) => walk_stmt(stmt, on_expr),
+                }
+            }
+        },
+        Stmt::While { condition, body, .. } => {
+            on_expr(condition);
+            walk_block(body, on_expr);
+        },
+        Stmt::Loop { body, .. } => walk_block(body, on_expr),
+        Stmt::FnDecl(fn_decl) => walk_block(&fn_decl.body, on_expr),
+        Stmt::Return { value: Some(value), .. } => on_expr(value),
+        Stmt::Return { value: None, .. } | Stmt::Break { .. } | Stmt::Continue { .. } => {}
+    }
+}
+
+/*
+ * Rejects number literals that don't fit their type. The type comes from
+ * the suffix, unsuffixed integers are `i64` and unsuffixed floats `f64`.
+ */
+pub fn check_literal_ranges(program: &Program, file_name: &str) -> Vec<hylo_error::Error> {
+    let mut errors = Vec::new();
+    for stmt in &program.statements {
+        walk_stmt(stmt, &mut |expr| check_literal_expr(expr, false, file_name, &mut errors));
+    }
+    return errors;
+}
+
+fn check_literal_expr(expr: &Expr, negated: bool, file_name: &str, errors: &mut Vec<hylo_error::Error>) {
+    match expr {
+        Expr::Literal(Literal::Int(int, span)) => {
+            let suffix = int.suffix.unwrap_or(NumberSuffix::I64);
+            let max = suffix.int_max().unwrap();
+            // `-128i8` is fine even though `128i8` is not
+            let limit = if negated && suffix.is_signed() { max + 1 } else { max };
+
+            if int.value > limit {
+                let mut error = hylo_error::Error::new(
+                    hylo_error::ErrorKind::LiteralOutOfRange,
+                    (*span).into(),
+                    Some(file_name)
+                ).add_msg(&format!("Literal out of range for `{}`", suffix.as_symbol()));
+
+                error = if suffix.is_signed() {
+                    error.add_note(&format!("`{}` can hold values from -{} to {}", suffix.as_symbol(), max + 1, max))
+                } else {
+                    error.add_note(&format!("`{}` can hold values from 0 to {}", suffix.as_symbol(), max))
+                };
+                if int.suffix.is_none() {
+                    error = error.add_note("Add a suffix such as `u64` to use a wider type");
+                }
+                errors.push(error);
+            }
+        },
+        Expr::Literal(Literal::Float(float, span)) => {
+            let suffix = float.suffix.unwrap_or(NumberSuffix::F64);
+            let is_infinite = match suffix {
+                NumberSuffix::F32 => (float.value as f32).is_infinite(),
+                _ => float.value.is_infinite()
+            };
+
+            if is_infinite {
+                errors.push(hylo_error::Error::new(
+                    hylo_error::ErrorKind::LiteralOutOfRange,
+                    (*span).into(),
+                    Some(file_name)
+                ).add_msg(&format!("Literal out of range for `{}`", suffix.as_symbol())));
+            }
+        },
+        Expr::Literal(_) => {},
+        Expr::Unary { op, expr } => check_literal_expr(expr, matches!(op, UnaryOp::Negative(_)), file_name, errors),
+        Expr::Binary { left, right, .. } => {
+            check_literal_expr(left, false, file_name, errors);
+            check_literal_expr(right, false, file_name, errors);
+        },
+        Expr::Call { callee, args, .. } => {
+            check_literal_expr(callee, false, file_name, errors);
+            for arg in args {
+                check_literal_expr(arg, false, file_name, errors);
+            }
+        },
+        Expr::Member { obj, .. } => check_literal_expr(obj, false, file_name, errors),
+        Expr::Index { obj, index, .. } => {
+            check_literal_expr(obj, false, file_name, errors);
+            check_literal_expr(index, false, file_name, errors);
+        },
+        Expr::Grouping { expr, .. } => check_literal_expr(expr, negated, file_name, errors),
+    }
+}
+
+// Calls `on_statements` with the statements of the program and of every block in it
+fn walk_statement_lists<'a>(statements: &'a [Stmt], on_statements: &mut dyn FnMut(&'a [Stmt])) {
+    on_statements(statements);
+    for stmt in statements {
+        match stmt {
+            Stmt::Block(block) => walk_statement_lists(&block.statements, on_statements),
+            Stmt::If { then_block, else_branch, .. } => {
+                walk_statement_lists(&then_block.statements, on_statements);
+                if let Some(else_branch) = else_branch {
+                    match &else_branch.body {
+                        ElseBody::Block(block) => walk_statement_lists(&block.statements, on_statements),
+                        ElseBody::If(stmt) => walk_statement_lists(std::slice::from_ref(stmt.as_ref()), on_statements),
+                    }
+                }
+            },
+            Stmt::While { body, .. } | Stmt::Loop { body, .. } => walk_statement_lists(&body.statements, on_statements),
+            Stmt::FnDecl(fn_decl) => walk_statement_lists(&fn_decl.body.statements, on_statements),
+            _ => {}
+        }
+    }
+}
+
+// Warnings for code that compiles but is most likely a mistake
+pub fn check_warnings(program: &Program, file_name: &str) -> Vec<hylo_error::Error> {
+    let mut warnings = Vec::new();
+    walk_statement_lists(&program.statements, &mut |statements| {
+        check_unreachable(statements, file_name, &mut warnings);
+        for stmt in statements {
+            check_condition(stmt, file_name, &mut warnings);
+        }
+    });
+    // Blocks are visited after the statements around them, report in source order
+    warnings.sort_by_key(|warning| warning.span.start);
+    return warnings;
+}
+
+// Statements after a `return`, `break` or `continue` in the same block never run
+fn check_unreachable(statements: &[Stmt], file_name: &str, warnings: &mut Vec<hylo_error::Error>) {
+    let exit = statements.iter().position(|stmt| {
+        matches!(stmt, Stmt::Return { .. } | Stmt::Break { .. } | Stmt::Continue { .. })
+    });
+    let Some(exit) = exit else { return };
+
+    // Functions are declarations, they are not run in place
+    let unreachable: Vec<&Stmt> = statements[exit + 1..].iter()
+        .filter(|stmt| !matches!(stmt, Stmt::FnDecl(_)))
+        .collect();
+    let (Some(first), Some(last)) = (unreachable.first(), unreachable.last()) else { return };
+
+    let keyword = match &statements[exit] {
+        Stmt::Return { .. } => "return",
+        Stmt::Break { .. } => "break",
+        _ => "continue",
+    };
+    warnings.push(hylo_error::Error::new(
+        hylo_error::ErrorKind::UnreachableCode,
+        Span { start: first.span().start, end: last.span().end }.into(),
+        Some(file_name)
+    ).add_msg("Unreachable code")
+    .add_note(&format!("Everything after `{}` in the same block is never run", keyword)));
+}
+
+// `while true` and conditions wrapped in parentheses
+fn check_condition(stmt: &Stmt, file_name: &str, warnings: &mut Vec<hylo_error::Error>) {
+    let (name, keyword, condition) = match stmt {
+        Stmt::If { keyword, condition, .. } => ("if", keyword, condition),
+        Stmt::While { keyword, condition, .. } => ("while", keyword, condition),
+        _ => return
+    };
+
+    if let Expr::Grouping { lparen, rparen, .. } = condition {
+        // `while(a)` needs a space once the parenthesis is gone
+        let lparen_replacement = if keyword.end + 1 == lparen.start { " " } else { "" };
+
+        warnings.push(hylo_error::Error::new(
+            hylo_error::ErrorKind::UnnecessaryParentheses,
+            Span { start: lparen.start, end: rparen.end }.into(),
+            Some(file_name)
+        ).add_msg(&format!("Unnecessary parentheses around `{}` condition", name))
+        .add_suggestion(hylo_error::Suggestion::new(
+            "Remove the parentheses",
+            lparen.start..lparen.end + 1,
+            lparen_replacement,
+            hylo_error::Applicability::MachineApplicable
+        ).add_edit(rparen.start..rparen.end + 1, "")));
+    }
+
+    let mut inner = condition;
+    while let Expr::Grouping { expr, .. } = inner {
+        inner = expr;
+    }
+    if let (Stmt::While { .. }, Expr::Literal(Literal::Bool(true, _))) = (stmt, inner) {
+        warnings.push(hylo_error::Error::new(
+            hylo_error::ErrorKind::WhileTrue,
+            Span { start: keyword.start, end: condition.span().end }.into(),
+            Some(file_name)
+        ).add_msg("Infinite loop written as `while true`")
+        .add_suggestion(hylo_error::Suggestion::new(
+            "Use `loop` for a loop that only ends with `break`",
+            keyword.start..condition.span().end + 1,
+            "loop",
+            hylo_error::Applicability::MachineApplicable
+        )));
+    }
+}
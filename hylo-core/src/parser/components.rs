@@ -1,13 +1,48 @@
+use crate::hylo_error;
+use crate::lexer::tokens::{FloatLiteral, IntLiteral};
+
 #[derive(Debug, Clone, Copy)]
 pub struct Span {
     pub start: usize,
     pub end: usize,
 }
 
+impl From<Span> for hylo_error::Span {
+    fn from(span: Span) -> Self {
+        hylo_error::Span { start: span.start, stop: span.end }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Ident {
+    pub name: String,
+    pub span: Span,
+}
+
+// `/// text` or `//! text`, without the leading slashes
+#[derive(Debug, Clone)]
+pub struct DocComment {
+    pub text: String,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct Type {
+    pub name: String,
+    pub span: Span,
+}
+
+// `: Type`
+#[derive(Debug)]
+pub struct TypeAnnotation {
+    pub colon: Span,
+    pub ty: Type,
+}
+
 #[derive(Debug)]
 pub enum Literal {
-    Int(i32, Span),
-    Float(f32, Span),
+    Int(IntLiteral, Span),
+    Float(FloatLiteral, Span),
     String(String, Span),
     Bool(bool, Span),
     Word(String, Span),
@@ -63,9 +98,262 @@ pub enum Expr {
         dot: Span,
         member: Box<Expr>
     },
-    EOL // END OF LINE
+    Index {
+        obj: Box<Expr>,
+        lbracket: Span,
+        index: Box<Expr>,
+        rbracket: Span,
+    },
+    Grouping {
+        lparen: Span,
+        expr: Box<Expr>,
+        rparen: Span,
+    },
+}
+
+impl Literal {
+    pub fn span(&self) -> Span {
+        match self {
+            Literal::Int(_, span)
+            | Literal::Float(_, span)
+            | Literal::String(_, span)
+            | Literal::Bool(_, span)
+            | Literal::Word(_, span) => *span,
+        }
+    }
+}
+
+impl UnaryOp {
+    pub fn span(&self) -> Span {
+        match self {
+            UnaryOp::Not(span) | UnaryOp::Negative(span) => *span,
+        }
+    }
+
+    pub fn as_symbol(&self) -> &'static str {
+        match self {
+            UnaryOp::Not(_) => "!",
+            UnaryOp::Negative(_) => "-",
+        }
+    }
+}
+
+impl BinaryOp {
+    pub fn span(&self) -> Span {
+        match self {
+            BinaryOp::Add(span)
+            | BinaryOp::Sub(span)
+            | BinaryOp::Mul(span)
+            | BinaryOp::Div(span)
+            | BinaryOp::Greater(span)
+            | BinaryOp::Less(span)
+            | BinaryOp::GreaterEqual(span)
+            | BinaryOp::LessEqual(span)
+            | BinaryOp::IsEqual(span)
+            | BinaryOp::IsNotEqual(span)
+            | BinaryOp::And(span)
+            | BinaryOp::Or(span) => *span,
+        }
+    }
+
+    pub fn as_symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::Add(_) => "+",
+            BinaryOp::Sub(_) => "-",
+            BinaryOp::Mul(_) => "*",
+            BinaryOp::Div(_) => "/",
+            BinaryOp::Greater(_) => ">",
+            BinaryOp::Less(_) => "<",
+            BinaryOp::GreaterEqual(_) => ">=",
+            BinaryOp::LessEqual(_) => "<=",
+            BinaryOp::IsEqual(_) => "==",
+            BinaryOp::IsNotEqual(_) => "!=",
+            BinaryOp::And(_) => "&&",
+            BinaryOp::Or(_) => "||",
+        }
+    }
+}
+
+impl Expr {
+    // Span covering the whole expression
+    pub fn span(&self) -> Span {
+        match self {
+            Expr::Literal(literal) => literal.span(),
+            Expr::Unary { op, expr } => Span { start: op.span().start, end: expr.span().end },
+            Expr::Binary { left, right, .. } => Span { start: left.span().start, end: right.span().end },
+            Expr::Call { callee, rparen, .. } => Span { start: callee.span().start, end: rparen.end },
+            Expr::Member { obj, member, .. } => Span { start: obj.span().start, end: member.span().end },
+            Expr::Index { obj, rbracket, .. } => Span { start: obj.span().start, end: rbracket.end },
+            Expr::Grouping { lparen, rparen, .. } => Span { start: lparen.start, end: rparen.end },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclKind {
+    Let,
+    Const,
+}
+
+impl DeclKind {
+    pub fn as_symbol(&self) -> &'static str {
+        match self {
+            DeclKind::Let => "let",
+            DeclKind::Const => "const",
+        }
+    }
+}
+
+// { statements }
+#[derive(Debug)]
+pub struct Block {
+    pub lbrace: Span,
+    pub statements: Vec<Stmt>,
+    pub rbrace: Span,
+}
+
+impl Block {
+    pub fn span(&self) -> Span {
+        Span { start: self.lbrace.start, end: self.rbrace.end }
+    }
 }
 
+#[derive(Debug)]
+pub struct ElseBranch {
+    pub keyword: Span,
+    pub body: ElseBody,
+}
+
+#[derive(Debug)]
+pub enum ElseBody {
+    Block(Block),
+    // else if ...
+    If(Box<Stmt>),
+}
+
+// name: Type
+#[derive(Debug)]
+pub struct Param {
+    pub name: Ident,
+    pub colon: Span,
+    pub ty: Type,
+}
+
+impl Param {
+    // Span from the parameter name to the end of its type
+    pub fn span(&self) -> Span {
+        Span { start: self.name.span.start, end: self.ty.span.end }
+    }
+}
+
+// -> Type
+#[derive(Debug)]
+pub struct ReturnType {
+    pub arrow: Span,
+    pub ty: Type,
+}
+
+// fn name(a: T, b: U) -> R { ... }
+#[derive(Debug)]
+pub struct FnDecl {
+    pub docs: Vec<DocComment>,
+    pub keyword: Span,
+    pub name: Ident,
+    pub lparen: Span,
+    pub params: Vec<Param>,
+    pub rparen: Span,
+    pub ret: Option<ReturnType>,
+    pub body: Block,
+}
+
+#[derive(Debug)]
 pub enum Stmt {
     ExprStmt { expr: Expr, semicolon: Span },
+
+    // let name: Type = init;
+    // const name: Type = init;
+    Let {
+        docs: Vec<DocComment>,
+        kind: DeclKind,
+        keyword: Span,
+        name: Ident,
+        ty: Option<TypeAnnotation>,
+        equals: Span,
+        init: Expr,
+        semicolon: Span,
+    },
+
+    // target = value;
+    Assign {
+        target: Expr,
+        equals: Span,
+        value: Expr,
+        semicolon: Span,
+    },
+
+    Block(Block),
+
+    // if condition { ... } else if condition { ... } else { ... }
+    If {
+        keyword: Span,
+        condition: Expr,
+        then_block: Block,
+        else_branch: Option<ElseBranch>,
+    },
+
+    // while condition { ... }
+    While {
+        keyword: Span,
+        condition: Expr,
+        body: Block,
+    },
+
+    // loop { ... }
+    Loop {
+        keyword: Span,
+        body: Block,
+    },
+
+    Break { keyword: Span, semicolon: Span },
+    Continue { keyword: Span, semicolon: Span },
+
+    FnDecl(FnDecl),
+
+    // return value;
+    Return {
+        keyword: Span,
+        value: Option<Expr>,
+        semicolon: Span,
+    },
+}
+
+impl Stmt {
+    // Span from the first token of the statement to its last
+    pub fn span(&self) -> Span {
+        match self {
+            Stmt::ExprStmt { expr, semicolon } => Span { start: expr.span().start, end: semicolon.end },
+            Stmt::Let { keyword, semicolon, .. } => Span { start: keyword.start, end: semicolon.end },
+            Stmt::Assign { target, semicolon, .. } => Span { start: target.span().start, end: semicolon.end },
+            Stmt::Block(block) => block.span(),
+            Stmt::If { keyword, then_block, else_branch, .. } => {
+                let end = match else_branch.as_ref().map(|else_branch| &else_branch.body) {
+                    Some(ElseBody::Block(block)) => block.rbrace.end,
+                    Some(ElseBody::If(stmt)) => stmt.span().end,
+                    None => then_block.rbrace.end,
+                };
+                Span { start: keyword.start, end }
+            },
+            Stmt::While { keyword, body, .. } | Stmt::Loop { keyword, body } => Span { start: keyword.start, end: body.rbrace.end },
+            Stmt::Break { keyword, semicolon } | Stmt::Continue { keyword, semicolon } => Span { start: keyword.start, end: semicolon.end },
+            Stmt::FnDecl(fn_decl) => Span { start: fn_decl.keyword.start, end: fn_decl.body.rbrace.end },
+            Stmt::Return { keyword, semicolon, .. } => Span { start: keyword.start, end: semicolon.end },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Program {
+    // `//!` comments at the start of the file
+    pub docs: Vec<DocComment>,
+    pub statements: Vec<Stmt>,
 }
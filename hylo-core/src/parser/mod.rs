@@ -1,2 +1,3 @@
 pub mod parser;
-pub mod components;
\ No newline at end of file
+pub mod components;
+pub mod checks;
\ No newline at end of file
@@ -1,25 +1,120 @@
-use crate::parser::components::{BinaryOp, Expr, Literal, Span, Stmt, UnaryOp};
-use crate::lexer::lexer::TokenContainer;
+use crate::parser::components::{BinaryOp, Block, DeclKind, DocComment, ElseBody, ElseBranch, Expr, FnDecl, Ident, Literal, Param, Program, ReturnType, Span, Stmt, Type, TypeAnnotation, UnaryOp};
+use crate::parser::checks;
+use crate::lexer::lexer::{Lexer, TokenContainer};
+use crate::lexer::source_code::SourceCodeContainer;
 use crate::lexer::tokens;
 use crate::hylo_error;
 
+/* 
+ * OPERATOR TABLE
+ *
+ * Every operator the expression parser understands is described by one
+ * entry. Binding powers come in (left, right) pairs: a left-associative
+ * operator has `right = left + 1`, a right-associative one `right = left`.
+ * Higher numbers bind tighter.
+ */
+#[derive(Clone, Copy)]
+enum PostfixKind {
+    Call,
+    Member,
+    Index
+}
+
+// (right binding power, constructor)
+type PrefixRule = (u8, fn(Span) -> UnaryOp);
+// (left binding power, right binding power, constructor)
+type InfixRule = (u8, u8, fn(Span) -> BinaryOp);
+// (left binding power, kind)
+type PostfixRule = (u8, PostfixKind);
+
+struct OperatorRule {
+    token: tokens::Token,
+    prefix: Option<PrefixRule>,
+    infix: Option<InfixRule>,
+    postfix: Option<PostfixRule>
+}
+
+const PREFIX_BP: u8 = 13;
+const POSTFIX_BP: u8 = 15;
+
+static OPERATOR_TABLE: &[OperatorRule] = &[
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::Or), prefix: None, infix: Some((1, 2, BinaryOp::Or)), postfix: None },
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::And), prefix: None, infix: Some((3, 4, BinaryOp::And)), postfix: None },
+    
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::IsEqual), prefix: None, infix: Some((5, 6, BinaryOp::IsEqual)), postfix: None },
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::IsNotEqual), prefix: None, infix: Some((5, 6, BinaryOp::IsNotEqual)), postfix: None },
+    
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::GreaterThan), prefix: None, infix: Some((7, 8, BinaryOp::Greater)), postfix: None },
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::LessThan), prefix: None, infix: Some((7, 8, BinaryOp::Less)), postfix: None },
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::GreaterThanOrEqual), prefix: None, infix: Some((7, 8, BinaryOp::GreaterEqual)), postfix: None },
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::LessThanOrEqual), prefix: None, infix: Some((7, 8, BinaryOp::LessEqual)), postfix: None },
+    
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::Plus), prefix: None, infix: Some((9, 10, BinaryOp::Add)), postfix: None },
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::Minus), prefix: Some((PREFIX_BP, UnaryOp::Negative)), infix: Some((9, 10, BinaryOp::Sub)), postfix: None },
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::Multiply), prefix: None, infix: Some((11, 12, BinaryOp::Mul)), postfix: None },
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::Divide), prefix: None, infix: Some((11, 12, BinaryOp::Div)), postfix: None },
+    
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::Exclamation), prefix: Some((PREFIX_BP, UnaryOp::Not)), infix: None, postfix: None },
+    
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::Dot), prefix: None, infix: None, postfix: Some((POSTFIX_BP, PostfixKind::Member)) },
+    OperatorRule { token: tokens::Token::Punctuation(tokens::Punctuation::LParen), prefix: None, infix: None, postfix: Some((POSTFIX_BP, PostfixKind::Call)) },
+    OperatorRule { token: tokens::Token::Punctuation(tokens::Punctuation::LBracket), prefix: None, infix: None, postfix: Some((POSTFIX_BP, PostfixKind::Index)) },
+    
+    // Not part of expressions, `=` is handled by assignment statements
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::Equals), prefix: None, infix: None, postfix: None },
+    OperatorRule { token: tokens::Token::Operator(tokens::Operator::Arrow), prefix: None, infix: None, postfix: None },
+];
+
+fn operator_rule(token: &tokens::Token) -> Option<&'static OperatorRule> {
+    OPERATOR_TABLE.iter().find(|rule| &rule.token == token)
+}
+
 /* HYLO PARSER */
 
-pub struct Parser {
+pub struct Parser<'a> {
     token_containers: Vec<TokenContainer>,
     pos: usize,
-    file_name: String
+    file_name: String,
+    // Where the errors of statements the parser recovered from go
+    diagnostics: &'a mut hylo_error::Diagnostics
 }
 
-impl Parser {
-    pub fn new(token_containers: Vec<TokenContainer>, file_name: &str) -> Self {
+impl<'a> Parser<'a> {
+    pub fn new(token_containers: Vec<TokenContainer>, file_name: &str, diagnostics: &'a mut hylo_error::Diagnostics) -> Self {
         Parser {
             token_containers,
             pos: 0,
-            file_name: String::from(file_name)
+            file_name: String::from(file_name),
+            diagnostics
         }
     }
     
+    /*
+     * Lexes and parses a whole file, returning its tokens with the program.
+     * The tokens the lexer recovered are parsed even when it reported errors,
+     * but the parser's syntax errors on a line with a lexing error are
+     * dropped: `let x = @;` is only reported for the '@', not as a missing
+     * expression as well.
+     */
+    pub fn parse_source(source_code: &SourceCodeContainer, file_name: &str, diagnostics: &mut hylo_error::Diagnostics) -> (Vec<TokenContainer>, Program) {
+        let mut lexer_diagnostics = hylo_error::Diagnostics::new();
+        let token_containers = Lexer::parse(source_code, file_name, &mut lexer_diagnostics);
+
+        let mut parser_diagnostics = hylo_error::Diagnostics::new();
+        let program = Parser::new(token_containers.clone(), file_name, &mut parser_diagnostics).parse_program();
+
+        let lexer_lines: Vec<usize> = lexer_diagnostics.iter()
+            .map(|e| source_code.get_line_and_column(e.span.start).0)
+            .collect();
+        diagnostics.extend(lexer_diagnostics.into_errors());
+        diagnostics.extend(parser_diagnostics.into_errors().into_iter().filter(|e| {
+            e.kind != hylo_error::ErrorKind::SyntaxError
+                || !lexer_lines.contains(&source_code.get_line_and_column(e.span.start).0)
+        }));
+        diagnostics.sort();
+        return (token_containers, program);
+    }
+    
     fn is_available(&self, pos: usize) -> bool {
         pos < self.token_containers.len()
     }
@@ -44,115 +139,465 @@ impl Parser {
         } else { return false }
     }
     
-    pub fn parse_program(&mut self) {
+    fn check_keyword(&self, keyword: tokens::Keyword) -> bool {
+        self.check(&tokens::Token::Keyword(keyword))
+    }
+    
+    // Consumes the current token and returns its span
+    fn advance_span(&mut self) -> Span {
+        let t_con = self.advance().unwrap();
+        Span { start: t_con.start, end: t_con.end }
+    }
+    
+    // Span of the most recently consumed token
+    fn previous_span(&self) -> Span {
+        match self.pos.checked_sub(1).and_then(|idx| self.token_containers.get(idx)) {
+            Some(t_con) => Span { start: t_con.start, end: t_con.end },
+            None => Span { start: 0, end: 0 }
+        }
+    }
+    
+    // Span of the current token, or of the last one at the end of the file
+    fn current_span(&self) -> Span {
+        match self.token_containers.get(self.pos) {
+            Some(t_con) => Span { start: t_con.start, end: t_con.end },
+            None => self.previous_span()
+        }
+    }
+    
+    /*
+     * Inserts a missing closing bracket after the last consumed token. That
+     * is only sure to be right when the statement ends right after it.
+     */
+    fn closing_suggestion(&self, msg: &str, bracket: &str) -> hylo_error::Suggestion {
+        let end = self.previous_span().end + 1;
+        let applicability = if self.check(&tokens::Token::Punctuation(tokens::Punctuation::Semicolon)) {
+            hylo_error::Applicability::MachineApplicable
+        } else {
+            hylo_error::Applicability::MaybeIncorrect
+        };
+        return hylo_error::Suggestion::new(msg, end..end, bracket, applicability);
+    }
+    
+    /*
+     * Parses the whole file. Errors go to the diagnostics given to `new`,
+     * the parser skips the statement they occurred in and carries on, so
+     * the program holds every statement that did parse. The diagnostics are
+     * left in source order, together with any that were there before.
+     */
+    pub fn parse_program(&mut self) -> Program {
         let mut statements = Vec::new();
+        let docs = self.parse_doc_comments(tokens::DocStyle::Inner);
+        
         while self.is_available(self.pos) {
-            statements.push(self.parse_statement());
+            if let Some(stmt) = self.parse_statement_or_recover() {
+                statements.push(stmt);
+            }
+        }
+        
+        let program = Program { docs, statements };
+        self.diagnostics.extend(checks::check_loop_control(&program, &self.file_name));
+        self.diagnostics.extend(checks::check_literal_ranges(&program, &self.file_name));
+        self.diagnostics.extend(checks::check_warnings(&program, &self.file_name));
+        self.diagnostics.sort();
+        return program;
+    }
+    
+    fn parse_statement_or_recover(&mut self) -> Option<Stmt> {
+        let start_pos = self.pos;
+        match self.parse_statement() {
+            Ok(stmt) => Some(stmt),
+            Err(e) => {
+                self.diagnostics.push(e);
+                // Always make progress, even when the error consumed nothing
+                if self.pos == start_pos {
+                    self.advance();
+                }
+                self.synchronize();
+                None
+            }
+        }
+    }
+    
+    /*
+     * Panic mode recovery: skips tokens up to and including the next ';',
+     * or up to a '}' or a keyword that starts a new statement. A block met
+     * on the way is skipped as a whole. Nothing is skipped when the ';' of
+     * the broken statement was already consumed.
+     */
+    fn synchronize(&mut self) {
+        if self.pos > 0 && self.token_containers[self.pos - 1].token == tokens::Token::Punctuation(tokens::Punctuation::Semicolon) {
+            return;
+        }
+        let mut depth: usize = 0;
+        while let Some(token) = self.peek() {
+            match token {
+                tokens::Token::Punctuation(tokens::Punctuation::LBrace) => depth += 1,
+                tokens::Token::Punctuation(tokens::Punctuation::RBrace) => {
+                    if depth == 0 { return; }
+                    depth -= 1;
+                    if depth == 0 {
+                        self.advance();
+                        return;
+                    }
+                },
+                tokens::Token::Punctuation(tokens::Punctuation::Semicolon) if depth == 0 => {
+                    self.advance();
+                    return;
+                },
+                tokens::Token::Keyword(keyword) if depth == 0 && *keyword != tokens::Keyword::Else => return,
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+    
+    // Collects consecutive doc comments of the given style
+    fn parse_doc_comments(&mut self, style: tokens::DocStyle) -> Vec<DocComment> {
+        let mut docs = Vec::new();
+        while let Some(tokens::Token::DocComment(c_style, text)) = self.peek() {
+            if *c_style != style { break }
+            
+            let text = text.clone();
+            docs.push(DocComment { text, span: self.advance_span() });
         }
+        return docs;
     }
     
-    fn parse_statement(&mut self) -> Stmt {
-        let r = self.parse_expr();
-        println!("{:?}", r);
+    fn parse_statement(&mut self) -> Result<Stmt, hylo_error::Error> {
+        let docs = self.parse_doc_comments(tokens::DocStyle::Outer);
         
-        todo!()
+        if self.check_keyword(tokens::Keyword::Let) {
+            return self.parse_declaration(DeclKind::Let, docs);
+        } else if self.check_keyword(tokens::Keyword::Const) {
+            return self.parse_declaration(DeclKind::Const, docs);
+        } else if self.check_keyword(tokens::Keyword::Fn) {
+            return Ok(Stmt::FnDecl(self.parse_fn_decl(docs)?));
+        } else if let Some(doc) = docs.first() {
+            return Err(hylo_error::Error::new(
+                hylo_error::ErrorKind::SyntaxError,
+                doc.span.into(),
+                Some(&self.file_name)
+            ).add_msg("Doc comment is not followed by a declaration")
+            .add_note("Doc comments document the `let`, `const` or `fn` after them, use `//` for a regular comment"));
+        }
+        
+        if let Some(tokens::Token::DocComment(tokens::DocStyle::Inner, _)) = self.peek() {
+            let span = self.advance_span();
+            return Err(hylo_error::Error::new(
+                hylo_error::ErrorKind::SyntaxError,
+                span.into(),
+                Some(&self.file_name)
+            ).add_msg("Inner doc comments are only allowed at the start of the file")
+            .add_note("Use `///` to document a declaration or `//` for a regular comment"));
+        }
+        
+        if self.check_keyword(tokens::Keyword::If) {
+            return self.parse_if();
+        } else if self.check_keyword(tokens::Keyword::While) {
+            let keyword = self.advance_span();
+            let condition = self.parse_expr()?;
+            let body = self.parse_block()?;
+            return Ok(Stmt::While { keyword, condition, body });
+        } else if self.check_keyword(tokens::Keyword::Loop) {
+            let keyword = self.advance_span();
+            let body = self.parse_block()?;
+            return Ok(Stmt::Loop { keyword, body });
+        } else if self.check_keyword(tokens::Keyword::Break) {
+            let keyword = self.advance_span();
+            let semicolon = self.expect_semicolon()?;
+            return Ok(Stmt::Break { keyword, semicolon });
+        } else if self.check_keyword(tokens::Keyword::Continue) {
+            let keyword = self.advance_span();
+            let semicolon = self.expect_semicolon()?;
+            return Ok(Stmt::Continue { keyword, semicolon });
+        } else if self.check_keyword(tokens::Keyword::Return) {
+            let keyword = self.advance_span();
+            let value = if self.check(&tokens::Token::Punctuation(tokens::Punctuation::Semicolon)) {
+                None
+            } else { Some(self.parse_expr()?) };
+            let semicolon = self.expect_semicolon()?;
+            return Ok(Stmt::Return { keyword, value, semicolon });
+        } else if self.check(&tokens::Token::Punctuation(tokens::Punctuation::LBrace)) {
+            return Ok(Stmt::Block(self.parse_block()?));
+        }
+        
+        let expr = self.parse_expr()?;
+        if self.check(&tokens::Token::Operator(tokens::Operator::Equals)) {
+            return self.parse_assignment(expr);
+        }
+        let semicolon = self.expect_semicolon()?;
+        
+        return Ok(Stmt::ExprStmt { expr, semicolon });
     }
     
-    fn parse_expr(&mut self) -> Result<Expr, hylo_error::Error> {
-        self.parse_term()
+    // { statements }
+    fn parse_block(&mut self) -> Result<Block, hylo_error::Error> {
+        if !self.check(&tokens::Token::Punctuation(tokens::Punctuation::LBrace)) {
+            let span = match self.token_containers.get(self.pos) {
+                Some(t_con) => Span { start: t_con.start, end: t_con.end },
+                None => self.previous_span()
+            };
+            return Err(hylo_error::Error::new(
+                hylo_error::ErrorKind::SyntaxError,
+                span.into(),
+                Some(&self.file_name)
+            ).add_msg("Expected '{' to start a block"));
+        }
+        let lbrace = self.advance_span();
+        
+        let mut statements = Vec::new();
+        while !self.check(&tokens::Token::Punctuation(tokens::Punctuation::RBrace)) {
+            if !self.is_available(self.pos) {
+                return Err(hylo_error::Error::new(
+                    hylo_error::ErrorKind::SyntaxError,
+                    lbrace.into(),
+                    Some(&self.file_name)
+                ).add_msg("Expected closing '}' of the block")
+                .add_note("Add a closing '}' at the end of the block"));
+            }
+            if let Some(stmt) = self.parse_statement_or_recover() {
+                statements.push(stmt);
+            }
+        }
+        let rbrace = self.advance_span();
+        
+        return Ok(Block { lbrace, statements, rbrace });
     }
     
-    fn parse_term(&mut self) -> Result<Expr, hylo_error::Error> {
-        let mut expr = self.parse_factor()?;
+    // if condition { ... } else if condition { ... } else { ... }
+    fn parse_if(&mut self) -> Result<Stmt, hylo_error::Error> {
+        let keyword = self.advance_span();
+        let condition = self.parse_expr()?;
+        let then_block = self.parse_block()?;
         
-        if self.peek().is_some() {
-            while self.peek().is_some() && matches!(self.peek().unwrap().clone(), tokens::Token::Operator(tokens::Operator::Plus) | tokens::Token::Operator(tokens::Operator::Minus)) {
-                let op_parsed = {
-                    let op_container = self.advance().unwrap();
-                    // Convertion of lexer operator into parser form
-                    match op_container.token {
-                        tokens::Token::Operator(tokens::Operator::Plus) => BinaryOp::Add(Span { start: op_container.start, end: op_container.end }),
-                        _ => BinaryOp::Sub(Span { start: op_container.start, end: op_container.end })
-                    }
-                };
+        let else_branch = if self.check_keyword(tokens::Keyword::Else) {
+            let else_keyword = self.advance_span();
+            let body = if self.check_keyword(tokens::Keyword::If) {
+                ElseBody::If(Box::new(self.parse_if()?))
+            } else {
+                ElseBody::Block(self.parse_block()?)
+            };
+            Some(ElseBranch { keyword: else_keyword, body })
+        } else { None };
+        
+        return Ok(Stmt::If { keyword, condition, then_block, else_branch });
+    }
+    
+    /* 
+     *  let name: Type = init;
+     *  const name: Type = init;
+     */
+    fn parse_declaration(&mut self, kind: DeclKind, docs: Vec<DocComment>) -> Result<Stmt, hylo_error::Error> {
+        let keyword = self.advance_span();
+        let name = self.expect_ident("Expected a variable name after the declaration keyword")?;
+        
+        let ty = if self.check(&tokens::Token::Punctuation(tokens::Punctuation::Colon)) {
+            let colon = self.advance_span();
+            let ty = self.parse_type("Expected a type after ':'")?;
+            Some(TypeAnnotation { colon, ty })
+        } else { None };
+        
+        if !self.check(&tokens::Token::Operator(tokens::Operator::Equals)) {
+            return Err(hylo_error::Error::new(
+                hylo_error::ErrorKind::SyntaxError,
+                self.previous_span().into(),
+                Some(&self.file_name)
+            ).add_msg("Expected '=' followed by an initial value")
+            .add_note(&format!("Variables must be initialized, e.g. `{} {} = ...;`", 
+                if kind == DeclKind::Let { "let" } else { "const" },
+                name.name
+            )));
+        }
+        let equals = self.advance_span();
+        let init = self.parse_expr()?;
+        let semicolon = self.expect_semicolon()?;
+        
+        return Ok(Stmt::Let { docs, kind, keyword, name, ty, equals, init, semicolon });
+    }
+    
+    // fn name(a: T, b: U) -> R { ... }
+    fn parse_fn_decl(&mut self, docs: Vec<DocComment>) -> Result<FnDecl, hylo_error::Error> {
+        let keyword = self.advance_span();
+        let name = self.expect_ident("Expected a function name after 'fn'")?;
+        
+        if !self.check(&tokens::Token::Punctuation(tokens::Punctuation::LParen)) {
+            return Err(hylo_error::Error::new(
+                hylo_error::ErrorKind::SyntaxError,
+                name.span.into(),
+                Some(&self.file_name)
+            ).add_msg("Expected '(' after the function name")
+            .add_note(&format!("Declare the parameters, e.g. `fn {}() {{ ... }}`", name.name)));
+        }
+        let lparen = self.advance_span();
+        
+        let mut params = Vec::new();
+        if !self.check(&tokens::Token::Punctuation(tokens::Punctuation::RParen)) {
+            loop {
+                params.push(self.parse_param()?);
                 
-                let rhs = self.parse_factor()?;
-                expr = Expr::Binary {
-                    left: Box::new(expr),
-                    op: op_parsed,
-                    right: Box::new(rhs)
+                if !self.check(&tokens::Token::Punctuation(tokens::Punctuation::Comma)) {
+                    break;
                 }
+                self.advance();
             }
         }
         
-        return Ok(expr);
+        if !self.check(&tokens::Token::Punctuation(tokens::Punctuation::RParen)) {
+            return Err(hylo_error::Error::new(
+                hylo_error::ErrorKind::SyntaxError,
+                lparen.into(),
+                Some(&self.file_name)
+            ).add_msg("Expected closing ')' of the parameter list")
+            .add_note("Separate parameters with ',' and close the list with ')'"));
+        }
+        let rparen = self.advance_span();
+        
+        let ret = if self.check(&tokens::Token::Operator(tokens::Operator::Arrow)) {
+            let arrow = self.advance_span();
+            let ty = self.parse_type("Expected a return type after '->'")?;
+            Some(ReturnType { arrow, ty })
+        } else { None };
+        
+        let body = self.parse_block()?;
+        
+        return Ok(FnDecl { docs, keyword, name, lparen, params, rparen, ret, body });
     }
     
-    fn parse_factor(&mut self) -> Result<Expr, hylo_error::Error> {
-        let mut expr = self.parse_unary()?;
+    // name: Type
+    fn parse_param(&mut self) -> Result<Param, hylo_error::Error> {
+        let name = self.expect_ident("Expected a parameter name")?;
         
-        if self.peek().is_some() {
-            while self.peek().is_some() && matches!(self.peek().unwrap().clone(), tokens::Token::Operator(tokens::Operator::Multiply) | tokens::Token::Operator(tokens::Operator::Divide)) {
-                let op_parsed = {
-                    let op_container = self.advance().unwrap();
-                    // Convertion of lexer operator into parser form
-                    match op_container.token {
-                        tokens::Token::Operator(tokens::Operator::Multiply) => BinaryOp::Mul(Span { start: op_container.start, end: op_container.end }),
-                        _ => BinaryOp::Div(Span { start: op_container.start, end: op_container.end })
-                    }
-                };
-                
-                let rhs = self.parse_unary()?;
+        if !self.check(&tokens::Token::Punctuation(tokens::Punctuation::Colon)) {
+            return Err(hylo_error::Error::new(
+                hylo_error::ErrorKind::SyntaxError,
+                name.span.into(),
+                Some(&self.file_name)
+            ).add_msg("Expected ':' followed by the parameter type")
+            .add_note(&format!("Parameters need a type, e.g. `{}: int`", name.name)));
+        }
+        let colon = self.advance_span();
+        let ty = self.parse_type("Expected a parameter type after ':'")?;
+        
+        return Ok(Param { name, colon, ty });
+    }
+    
+    fn parse_type(&mut self, msg: &str) -> Result<Type, hylo_error::Error> {
+        let type_name = self.expect_ident(msg)?;
+        return Ok(Type { name: type_name.name, span: type_name.span });
+    }
+    
+    // target = value;
+    fn parse_assignment(&mut self, target: Expr) -> Result<Stmt, hylo_error::Error> {
+        let is_place = matches!(target, 
+            Expr::Literal(Literal::Word(..)) | Expr::Member { .. } | Expr::Index { .. }
+        );
+        if !is_place {
+            return Err(hylo_error::Error::new(
+                hylo_error::ErrorKind::InvalidAssignmentTarget,
+                target.span().into(),
+                Some(&self.file_name)
+            ).add_msg("Cannot assign to this expression")
+            .add_note("Only variables, members (`a.b`) and index expressions (`a[i]`) can be assigned to"));
+        }
+        
+        let equals = self.advance_span();
+        let value = self.parse_expr()?;
+        let semicolon = self.expect_semicolon()?;
+        
+        return Ok(Stmt::Assign { target, equals, value, semicolon });
+    }
+    
+    fn expect_ident(&mut self, msg: &str) -> Result<Ident, hylo_error::Error> {
+        if let Some(tokens::Token::Word(name)) = self.peek() {
+            let name = name.clone();
+            let span = self.advance_span();
+            return Ok(Ident { name, span });
+        }
+        
+        let error = hylo_error::Error::new(
+            hylo_error::ErrorKind::SyntaxError,
+            self.current_span().into(),
+            Some(&self.file_name)
+        ).add_msg(msg);
+        
+        if let Some(tokens::Token::Keyword(keyword)) = self.peek() {
+            return Err(error.add_note(&format!("`{}` is a reserved keyword and cannot be used as a name", keyword.as_symbol())));
+        }
+        return Err(error);
+    }
+    
+    fn expect_semicolon(&mut self) -> Result<Span, hylo_error::Error> {
+        if !self.check(&tokens::Token::Punctuation(tokens::Punctuation::Semicolon)) {
+            let prev_span = self.previous_span();
+            return Err(hylo_error::Error::new(
+                hylo_error::ErrorKind::SyntaxError,
+                prev_span.into(),
+                Some(&self.file_name)
+            ).add_msg("Expected ';' at the end of the statement")
+            .add_suggestion(hylo_error::Suggestion::new(
+                "Add a ';' after the statement",
+                prev_span.end + 1..prev_span.end + 1,
+                ";",
+                hylo_error::Applicability::MachineApplicable
+            )));
+        }
+        
+        return Ok(self.advance_span());
+    }
+    
+    fn parse_expr(&mut self) -> Result<Expr, hylo_error::Error> {
+        self.parse_expr_bp(0)
+    }
+    
+    /* 
+     *  Pratt parser: parses an expression whose operators all bind
+     *  at least as tightly as `min_bp`. Operator behaviour comes from
+     *  OPERATOR_TABLE.
+     */
+    fn parse_expr_bp(&mut self, min_bp: u8) -> Result<Expr, hylo_error::Error> {
+        let prefix = self.peek().and_then(operator_rule).and_then(|rule| rule.prefix);
+        let mut expr = match prefix {
+            Some((r_bp, make_op)) => {
+                let op_span = self.advance_span();
+                let operand = self.parse_expr_bp(r_bp)?;
+                Expr::Unary {
+                    op: make_op(op_span),
+                    expr: Box::new(operand)
+                }
+            },
+            None => self.parse_primary()?
+        };
+        
+        while let Some(rule) = self.peek().and_then(operator_rule) {
+            if let Some((l_bp, kind)) = rule.postfix {
+                if l_bp < min_bp { break }
+                expr = self.parse_postfix(expr, kind)?;
+            } else if let Some((l_bp, r_bp, make_op)) = rule.infix {
+                if l_bp < min_bp { break }
+                let op_span = self.advance_span();
+                let rhs = self.parse_expr_bp(r_bp)?;
                 expr = Expr::Binary {
                     left: Box::new(expr),
-                    op: op_parsed,
+                    op: make_op(op_span),
                     right: Box::new(rhs)
                 };
-            }
+            } else { break }
         }
         
         return Ok(expr);
     }
     
-    fn parse_unary(&mut self) -> Result<Expr, hylo_error::Error> {
-        match self.peek() {
-            Some(c_token) => {
-                if matches!(c_token, tokens::Token::Operator(tokens::Operator::Minus) | tokens::Token::Operator(tokens::Operator::Exclamation)) {
-                    let op_parsed = {
-                        let op_container = self.advance().unwrap();
-                        // Convertion of lexer operator into parser form
-                        match op_container.token {
-                            tokens::Token::Operator(tokens::Operator::Minus) => UnaryOp::Negative(Span { start: op_container.start, end: op_container.end }),
-                            _ => UnaryOp::Not(Span { start: op_container.start, end: op_container.end })
-                        }
-                    };
-                    
-                    let expr = self.parse_unary()?;
-                    return Ok(Expr::Unary {
-                        op: op_parsed,
-                        expr: Box::new(expr)
-                    });
-                } else { return self.parse_postfix() }
-            },
-            None => { return self.parse_postfix() }
-        };
-        
-    }
     /* 
      *  This function includes support for parsing
      *  - Functional calls
      *  - Dot Operation
+     *  - Indexing
      */
-    fn parse_postfix(&mut self) -> Result<Expr, hylo_error::Error> {
-        let mut expr = self.parse_primary()?;
-        
-        loop {
-            // Parsing Functional calls
-            if self.check(&tokens::Token::Punctuation(tokens::Punctuation::LParen)) {
-                let lparen_span = {
-                    let lparen_con = self.advance().unwrap();
-                    Span { start: lparen_con.start, end: lparen_con.end }
-                };
+    fn parse_postfix(&mut self, expr: Expr, kind: PostfixKind) -> Result<Expr, hylo_error::Error> {
+        match kind {
+            PostfixKind::Call => {
+                let lparen_span = self.advance_span();
                 
                 let mut args: Vec<Expr> = Vec::new();
                 if !self.check(&tokens::Token::Punctuation(tokens::Punctuation::RParen)) {
@@ -166,46 +611,71 @@ impl Parser {
                     } 
                 }
                 
-                let rparen_span = {
-                    if !self.check(&tokens::Token::Punctuation(tokens::Punctuation::RParen)) {
-                        return Err(hylo_error::Error::new(
-                            hylo_error::ErrorKind::SyntaxError,
-                            hylo_error::Span { start: lparen_span.start, stop: lparen_span.end},
-                            Some(&self.file_name)
-                        ).add_msg("Expected clossing ')' of the functional call")
-                        .add_note("Add a closing ')' before the end of the functional call"));
-                    }
-                    
-                    let t_con = self.advance().unwrap();
-                    Span { start: t_con.start, end: t_con.end }
-                };
+                if !self.check(&tokens::Token::Punctuation(tokens::Punctuation::RParen)) {
+                    return Err(hylo_error::Error::new(
+                        hylo_error::ErrorKind::SyntaxError,
+                        self.current_span().into(),
+                        Some(&self.file_name)
+                    ).add_msg("Expected closing ')' of the function call")
+                    .add_label(lparen_span.into(), "The call is opened here")
+                    .add_suggestion(self.closing_suggestion("Add a closing ')' after the last argument", ")")));
+                }
+                let rparen_span = self.advance_span();
                 
-                expr = Expr::Call {
+                return Ok(Expr::Call {
                     callee: Box::new(expr),
                     lparen: lparen_span,
                     args: args,
                     rparen: rparen_span
-                }
-            } else if self.check(&tokens::Token::Operator(tokens::Operator::Dot)) { 
-                let dot_span = {
-                    let dot_con = self.advance().unwrap();
-                    Span { start: dot_con.start, end: dot_con.end }
-                };
-                let member = self.parse_expr()?;
+                });
+            },
+            PostfixKind::Member => {
+                let dot_span = self.advance_span();
+                let member = self.expect_ident("Expected a member name after '.'")?;
                 
-                expr = Expr::Member {
+                return Ok(Expr::Member {
                     obj: Box::new(expr),
                     dot: dot_span,
-                    member: Box::new(member)
-                };
+                    member: Box::new(Expr::Literal(Literal::Word(member.name, member.span)))
+                });
+            },
+            PostfixKind::Index => {
+                let lbracket_span = self.advance_span();
+                let index = self.parse_expr()?;
                 
-            } else { break }
+                if !self.check(&tokens::Token::Punctuation(tokens::Punctuation::RBracket)) {
+                    return Err(hylo_error::Error::new(
+                        hylo_error::ErrorKind::SyntaxError,
+                        self.current_span().into(),
+                        Some(&self.file_name)
+                    ).add_msg("Expected closing ']' of the index expression")
+                    .add_label(lbracket_span.into(), "The index is opened here")
+                    .add_suggestion(self.closing_suggestion("Add a closing ']' after the index", "]")));
+                }
+                let rbracket_span = self.advance_span();
+                
+                return Ok(Expr::Index {
+                    obj: Box::new(expr),
+                    lbracket: lbracket_span,
+                    index: Box::new(index),
+                    rbracket: rbracket_span
+                });
+            }
         }
-        return Ok(expr);
     }
     
+    // `found` describes the token in the way, e.g. "`;`"
+    fn expected_expression(&self, span: Span, found: &str) -> hylo_error::Error {
+        return hylo_error::Error::new(
+            hylo_error::ErrorKind::SyntaxError,
+            span.into(),
+            Some(&self.file_name)
+        ).add_msg(&format!("Expected an expression, found {}", found));
+    }
+    
+    // Only consumes the token when it starts an expression, so recovery starts at it
     fn parse_primary(&mut self) -> Result<Expr, hylo_error::Error> {
-        if let Some(token_con) = self.advance() {
+        if let Some(token_con) = self.token_containers.get(self.pos).cloned() {
             let pos = Span {
                 start: token_con.start,
                 end: token_con.end
@@ -217,17 +687,52 @@ impl Parser {
                 tokens::Token::String(value) => Expr::Literal(Literal::String(value, pos)),
                 tokens::Token::Boolean(value) => Expr::Literal(Literal::Bool(value, pos)),
                 tokens::Token::Word(value) => Expr::Literal(Literal::Word(value, pos)),
-                unknown_token => {
+                tokens::Token::Punctuation(tokens::Punctuation::LParen) => {
+                    self.advance();
+                    let expr = self.parse_expr()?;
+                    if !self.check(&tokens::Token::Punctuation(tokens::Punctuation::RParen)) {
+                        return Err(hylo_error::Error::new(
+                            hylo_error::ErrorKind::SyntaxError,
+                            pos.into(),
+                            Some(&self.file_name)
+                        ).add_msg("Expected closing ')' of the parenthesized expression")
+                        .add_note("Add a closing ')' after the expression"));
+                    }
+                    
+                    return Ok(Expr::Grouping {
+                        lparen: pos,
+                        expr: Box::new(expr),
+                        rparen: self.advance_span()
+                    });
+                },
+                tokens::Token::Keyword(keyword) => {
                     return Err(hylo_error::Error::new(
                         hylo_error::ErrorKind::SyntaxError,
-                        hylo_error::Span { start: token_con.start, stop: token_con.end},
+                        pos.into(),
                         Some(&self.file_name)
-                    ).add_msg(&format!("Unexpected token: {:?}", unknown_token))); // TODO: Add formating to the unknown_token
+                    ).add_msg(&format!("Expected an expression, found keyword `{}`", keyword.as_symbol()))
+                    .add_note(&format!("`{}` is a reserved keyword and cannot be used as a name", keyword.as_symbol())));
+                },
+                tokens::Token::Operator(operator) => {
+                    return Err(self.expected_expression(pos, &format!("`{}`", operator.as_symbol())));
+                },
+                tokens::Token::Punctuation(punctuation) => {
+                    return Err(self.expected_expression(pos, &format!("`{}`", punctuation.as_symbol())));
+                },
+                tokens::Token::DocComment(..) => {
+                    return Err(self.expected_expression(pos, "a doc comment"));
                 }
             };
             
+            self.advance();
             return Ok(parsed_primary);
-        } else { return Ok(Expr::EOL) }
+        } else {
+            return Err(hylo_error::Error::new(
+                hylo_error::ErrorKind::SyntaxError,
+                self.previous_span().into(),
+                Some(&self.file_name)
+            ).add_msg("Expected an expression but reached the end of the file"));
+        }
     }
     
 }
\ No newline at end of file
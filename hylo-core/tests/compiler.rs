@@ -0,0 +1,52 @@
+use hylo_core::compiler::HyloCompiler;
+use hylo_core::lexer::source_code::SourceCodeContainer;
+use hylo_core::hylo_error::{Diagnostics, ErrorKind};
+
+fn compile(source: &str) -> Result<usize, Diagnostics> {
+    let source_code = SourceCodeContainer::from(String::from(source));
+    let compilation = HyloCompiler::new().compile(&source_code, "test.hy")?;
+    Ok(compilation.program.statements.len())
+}
+
+#[test]
+fn compile_returns_the_program() {
+    assert_eq!(compile("let a = 1;\nlet b = a + 2;\n").unwrap(), 2);
+}
+
+#[test]
+fn compile_returns_errors_instead_of_exiting() {
+    let diagnostics = compile("let a = ;\nbreak;\n").unwrap_err();
+    let kinds: Vec<ErrorKind> = diagnostics.iter().map(|e| e.kind.clone()).collect();
+    assert_eq!(kinds, vec![ErrorKind::SyntaxError, ErrorKind::LoopControlOutsideLoop]);
+    assert_eq!(diagnostics.exit_code(), ErrorKind::SyntaxError.exit_code());
+
+    // The tokens around a lexing error are still parsed
+    let diagnostics = compile("let b = ;\nlet a = \"text;\n").unwrap_err();
+    let kinds: Vec<ErrorKind> = diagnostics.iter().map(|e| e.kind.clone()).collect();
+    assert_eq!(kinds, vec![ErrorKind::SyntaxError, ErrorKind::StringNotTerminated]);
+
+    // Without a second report for the token the lexer dropped
+    let diagnostics = compile("let x = @;\nlet y = 1 +;\n").unwrap_err();
+    let starts: Vec<usize> = diagnostics.iter().map(|e| e.span.start).collect();
+    assert_eq!(starts, vec![8, 22]);
+}
+
+#[test]
+fn errors_display_on_one_line() {
+    let diagnostics = compile("let a = ;").unwrap_err();
+    let error = diagnostics.iter().next().unwrap();
+    assert_eq!(error.to_string(), format!("E0001 SyntaxError in test.hy: {}", error.message));
+    assert_eq!(diagnostics.to_string(), error.to_string());
+}
+
+#[test]
+fn errors_work_with_the_question_mark_operator() {
+    fn check(source: &str) -> Result<(), Box<dyn std::error::Error>> {
+        compile(source)?;
+        Ok(())
+    }
+
+    assert!(check("let a = 1;").is_ok());
+    let error = check("let a = ;").unwrap_err();
+    assert!(error.to_string().starts_with("E0001 SyntaxError in test.hy: "));
+}
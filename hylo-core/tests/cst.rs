@@ -0,0 +1,139 @@
+use hylo_core::cst::{NodeKind, SyntaxNode, SyntaxTokens, SyntaxTree, TriviaKind};
+use hylo_core::hylo_error::Diagnostics;
+use hylo_core::lexer::source_code::SourceCodeContainer;
+
+fn parse(source: &str) -> (SyntaxTree, Diagnostics) {
+    let source_code = SourceCodeContainer::from(String::from(source));
+    SyntaxTree::parse(&source_code, "test.hy")
+}
+
+fn tree(source: &str) -> SyntaxTree {
+    let (tree, diagnostics) = parse(source);
+    assert!(!diagnostics.has_errors(), "parsing failed: {:?}", diagnostics);
+    tree
+}
+
+fn kinds(node: &SyntaxNode) -> Vec<NodeKind> {
+    node.child_nodes().map(|child| child.kind).collect()
+}
+
+// The kinds of `node` and everything below it
+fn all_kinds(node: &SyntaxNode) -> Vec<NodeKind> {
+    let mut kinds = vec![node.kind];
+    for child in node.child_nodes() {
+        kinds.extend(all_kinds(child));
+    }
+    kinds
+}
+
+const SOURCES: &[&str] = &[
+    "",
+    "\n\n",
+    "   ",
+    "// just a comment",
+    "x;",
+    "x;\n",
+    "let  x:int=1 ;   // trailing\n\n\n/* block */ x = x+1;",
+    "//! File docs\n\n/// Adds\nfn add(a: int,b: int) -> int {\n\treturn a + b; /* nested /* comment */ */\n}\n",
+    "if a { b; }\r\nelse if c {\r\n    d;\r\n} else { e; }\r\n",
+    "while  i < 10 {\n  i = i + 1 ;\n  if i == 5 { continue ; }\n}\nloop { break; }",
+    "print(\"héllo\\n\", 'wörld', 0xFF_u8, 2.5e3f32, a[0]) ;   ",
+    "f(a, /* inline */ b,\n  c)(d).e[f];\n// end\n",
+    "let 名前 = \"日本語 😀\"; // unicode",
+];
+
+#[test]
+fn token_stream_round_trips() {
+    for source in SOURCES {
+        let source_code = SourceCodeContainer::from(String::from(*source));
+        let tokens = SyntaxTokens::parse(&source_code, "test.hy").unwrap();
+        assert_eq!(tokens.text(), *source);
+    }
+}
+
+const BROKEN_SOURCES: &[&str] = &[
+    "let a = 1 + ;\nlet b = 2;\n",
+    "let x = @;\nfoo(;\n",
+    "while x { let = 1; y = 2; }\nbreak;",
+    "let s = \"never closed\nlet t = 1;",
+    "fn f( {\n}\n/* open",
+    "let 名前 = \"😀\" + # ;\r\nx = 1\n",
+    "}\n{",
+];
+
+#[test]
+fn tree_round_trips() {
+    for source in SOURCES {
+        let tree = tree(source);
+        assert_eq!(tree.text(), *source);
+        assert!(!all_kinds(&tree.root).contains(&NodeKind::Error), "{:?}", source);
+    }
+}
+
+#[test]
+fn trees_of_files_with_errors_round_trip() {
+    for source in BROKEN_SOURCES {
+        let (tree, diagnostics) = parse(source);
+        assert!(diagnostics.has_errors(), "{:?}", source);
+        assert_eq!(tree.text(), *source);
+    }
+}
+
+#[test]
+fn skipped_statements_become_error_nodes() {
+    let (tree, diagnostics) = parse("let a = 1 + ;\nlet b = 2;\nwhile x { let = 1; y = 2; }");
+    assert_eq!(diagnostics.error_count(), 2);
+    assert_eq!(kinds(&tree.root), vec![NodeKind::Error, NodeKind::Let, NodeKind::While]);
+    assert_eq!(tree.root.child_nodes().next().unwrap().text(), "let a = 1 + ;");
+
+    // Recovery inside a block keeps the statements after the error
+    let body = tree.root.child_nodes().last().unwrap().child_nodes().last().unwrap();
+    assert_eq!(kinds(body), vec![NodeKind::Error, NodeKind::Assign]);
+    assert_eq!(body.child_nodes().next().unwrap().text(), "let = 1; ");
+
+    // Text the lexer rejected stays in the trivia
+    let (tree, _) = parse("let x = @;");
+    let equals = tree.root.child_nodes().next().unwrap().child_tokens().nth(2).unwrap();
+    let trailing: Vec<(TriviaKind, &str)> = equals.trailing_trivia.iter().map(|t| (t.kind, t.text.as_str())).collect();
+    assert_eq!(trailing, vec![(TriviaKind::Whitespace, " "), (TriviaKind::Skipped, "@")]);
+}
+
+#[test]
+fn trivia_is_split_at_the_first_newline() {
+    let source_code = SourceCodeContainer::from(String::from("a; // one\n  // two\nb;"));
+    let tokens = SyntaxTokens::parse(&source_code, "test.hy").unwrap().tokens;
+
+    let semicolon = &tokens[1];
+    let trailing: Vec<TriviaKind> = semicolon.trailing_trivia.iter().map(|t| t.kind).collect();
+    assert_eq!(trailing, vec![TriviaKind::Whitespace, TriviaKind::LineComment]);
+
+    let b = &tokens[2];
+    let leading: Vec<(TriviaKind, &str)> = b.leading_trivia.iter().map(|t| (t.kind, t.text.as_str())).collect();
+    assert_eq!(leading, vec![
+        (TriviaKind::Newline, "\n"),
+        (TriviaKind::Whitespace, "  "),
+        (TriviaKind::LineComment, "// two"),
+        (TriviaKind::Newline, "\n"),
+    ]);
+}
+
+#[test]
+fn tree_follows_the_ast() {
+    let tree = tree("/// docs\nfn f(a: int, b: int) -> int { return a + g(b, 1); }\nx = 1;");
+    assert_eq!(kinds(&tree.root), vec![NodeKind::FnDecl, NodeKind::Assign]);
+
+    let fn_decl = tree.root.child_nodes().next().unwrap();
+    assert_eq!(kinds(fn_decl), vec![NodeKind::Param, NodeKind::Param, NodeKind::ReturnType, NodeKind::Block]);
+    // The doc comment, `fn`, name, parens and the comma between parameters
+    let tokens: Vec<&str> = fn_decl.child_tokens().map(|t| t.text.as_str()).collect();
+    assert_eq!(tokens, vec!["/// docs", "fn", "f", "(", ",", ")"]);
+
+    let body = fn_decl.child_nodes().last().unwrap();
+    let ret = body.child_nodes().next().unwrap();
+    assert_eq!(ret.kind, NodeKind::Return);
+    assert_eq!(ret.text(), "return a + g(b, 1); ");
+
+    let call = ret.child_nodes().next().unwrap().child_nodes().nth(1).unwrap();
+    assert_eq!(call.kind, NodeKind::Call);
+    assert_eq!(call.text(), "g(b, 1)");
+}
@@ -0,0 +1,57 @@
+use hylo_core::compiler::HyloCompiler;
+use hylo_core::hylo_error::{index, ErrorKind};
+use hylo_core::lexer::source_code::SourceCodeContainer;
+
+// The code of the ```hylo block under `heading`
+fn example(explanation: &str, heading: &str) -> String {
+    let section = explanation.split(heading).nth(1)
+        .unwrap_or_else(|| panic!("no `{}` section", heading));
+    let start = section.find("```hylo\n").expect("no hylo code block") + "```hylo\n".len();
+    let end = start + section[start..].find("```").expect("unclosed code block");
+    return String::from(&section[start..end]);
+}
+
+// Every code the example gets, errors and warnings alike
+fn codes(source: &str) -> Vec<&'static str> {
+    let source_code = SourceCodeContainer::from(String::from(source));
+    let diagnostics = match HyloCompiler::new().compile(&source_code, "example.hy") {
+        Ok(compilation) => compilation.warnings,
+        Err(diagnostics) => diagnostics,
+    };
+    return diagnostics.iter().map(|e| e.kind.code()).collect();
+}
+
+#[test]
+fn every_code_is_explained() {
+    for kind in ErrorKind::ALL {
+        let explanation = index::explanation(&kind);
+        let title = format!("# {}: {:?}\n", kind.code(), kind);
+        assert!(explanation.starts_with(&title), "{} starts with {:?}", kind.code(), explanation.lines().next());
+    }
+}
+
+#[test]
+fn wrong_examples_get_their_code() {
+    for kind in ErrorKind::ALL {
+        let wrong = example(index::explanation(&kind), "## Wrong");
+        let codes = codes(&wrong);
+        assert!(codes.contains(&kind.code()), "{} example gets {:?}:\n{}", kind.code(), codes, wrong);
+    }
+}
+
+#[test]
+fn correct_examples_are_clean() {
+    for kind in ErrorKind::ALL {
+        let correct = example(index::explanation(&kind), "## Correct");
+        let codes = codes(&correct);
+        assert!(codes.is_empty(), "{} fixed example gets {:?}:\n{}", kind.code(), codes, correct);
+    }
+}
+
+#[test]
+fn codes_are_looked_up_in_any_case() {
+    assert_eq!(ErrorKind::from_code("E0002"), Some(ErrorKind::StringNotTerminated));
+    assert_eq!(ErrorKind::from_code("w0001"), Some(ErrorKind::UnreachableCode));
+    assert_eq!(ErrorKind::from_code("E9999"), None);
+    assert_eq!(ErrorKind::from_code("unreachable_code"), None);
+}
@@ -0,0 +1,96 @@
+use hylo_core::formatter::{format_source, FormatOptions};
+use hylo_core::lexer::source_code::SourceCodeContainer;
+
+use std::path::PathBuf;
+
+fn format_with(source: &str, options: &FormatOptions) -> String {
+    let source_code = SourceCodeContainer::from(String::from(source));
+    format_source(&source_code, "test.hy", options).expect("formatting failed")
+}
+
+fn format(source: &str) -> String {
+    format_with(source, &FormatOptions::default())
+}
+
+// Every `<name>.in.hy` of the corpus with its `<name>.out.hy`
+fn corpus() -> Vec<(PathBuf, String, String)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fmt");
+    let mut cases = Vec::new();
+    for entry in std::fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_str().unwrap().to_string();
+        if let Some(stem) = name.strip_suffix(".in.hy") {
+            let input = std::fs::read_to_string(&path).unwrap();
+            let expected = std::fs::read_to_string(dir.join(format!("{}.out.hy", stem))).unwrap();
+            cases.push((path, input, expected));
+        }
+    }
+    assert!(!cases.is_empty());
+    cases
+}
+
+#[test]
+fn corpus_matches_expected_output() {
+    for (path, input, expected) in corpus() {
+        assert_eq!(format(&input), expected, "{}", path.display());
+    }
+}
+
+#[test]
+fn formatting_is_idempotent() {
+    let mut sources: Vec<String> = Vec::new();
+    for (_, input, expected) in corpus() {
+        sources.push(input);
+        sources.push(expected);
+    }
+    sources.extend([
+        "",
+        "// only a comment",
+        "x;\n\n\n\ny;",
+        "f(a // why\n, b);",
+        "let x =\n/* odd */\n5;",
+        "a // one\n+ b;",
+        "if a { } else { // empty\n}",
+    ].map(String::from));
+
+    for source in sources {
+        let once = format(&source);
+        assert_eq!(format(&once), once, "{:?}", source);
+    }
+}
+
+#[test]
+fn comments_are_kept() {
+    for (path, input, _) in corpus() {
+        let output = format(&input);
+        for line in input.lines() {
+            if let Some(idx) = line.find("//").or(line.find("/*")) {
+                let comment = line[idx..].trim_end();
+                assert!(output.contains(comment), "{}: lost {:?}", path.display(), comment);
+            }
+        }
+    }
+}
+
+#[test]
+fn width_is_configurable() {
+    let source = "call(first, second);";
+    assert_eq!(format(source), "call(first, second);\n");
+
+    let narrow = FormatOptions { max_width: 12, indent_width: 2 };
+    assert_eq!(format_with(source, &narrow), "call(\n  first,\n  second\n);\n");
+}
+
+#[test]
+fn empty_lines_are_collapsed() {
+    assert_eq!(format("\n\nx;\n\n\n\ny;\nz;\n\n"), "x;\n\ny;\nz;\n");
+    assert_eq!(format("{\n\n  x;\n\n}"), "{\n    x;\n}\n");
+    assert_eq!(format(""), "");
+}
+
+#[test]
+fn operators_that_would_merge_keep_a_space() {
+    assert_eq!(format("let a = - -b;"), "let a = - -b;\n");
+    assert_eq!(format("let a = ! !b;"), "let a = ! !b;\n");
+    assert_eq!(format("let a = -(b);"), "let a = -(b);\n");
+}
@@ -0,0 +1,55 @@
+use hylo_core::emit;
+use hylo_core::hylo_error::Diagnostics;
+use hylo_core::lexer::lexer::Lexer;
+use hylo_core::lexer::source_code::SourceCodeContainer;
+use hylo_core::parser::parser::Parser;
+
+use std::path::{Path, PathBuf};
+
+/*
+ * Every `tests/golden/<name>.hy` is dumped with each `--emit` kind and
+ * compared against `<name>.tokens`, `<name>.ast` and `<name>.ast.json`.
+ * Run with `UPDATE_GOLDEN=1` to write the current output instead.
+ */
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+fn sources() -> Vec<PathBuf> {
+    let mut sources: Vec<PathBuf> = std::fs::read_dir(golden_dir()).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "hy"))
+        .collect();
+    sources.sort();
+    assert!(!sources.is_empty());
+    sources
+}
+
+fn check_golden(path: &Path, actual: &str) {
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(path)
+        .unwrap_or_else(|_| panic!("missing {}, run with UPDATE_GOLDEN=1 to create it", path.display()));
+    assert_eq!(actual, expected, "{} is out of date, run with UPDATE_GOLDEN=1 to update it", path.display());
+}
+
+#[test]
+fn emitted_stages_match_golden_files() {
+    for source_path in sources() {
+        let source = std::fs::read_to_string(&source_path).unwrap();
+        let source_code = SourceCodeContainer::from(source);
+
+        let mut diagnostics = Diagnostics::new();
+        let token_containers = Lexer::parse(&source_code, "golden.hy", &mut diagnostics);
+        assert!(!diagnostics.has_errors(), "lexing failed: {:?}", diagnostics);
+        check_golden(&source_path.with_extension("tokens"), &emit::dump_tokens(&token_containers, &source_code));
+
+        let program = Parser::new(token_containers, "golden.hy", &mut diagnostics).parse_program();
+        assert!(!diagnostics.has_errors(), "parsing failed: {:?}", diagnostics);
+        check_golden(&source_path.with_extension("ast"), &emit::dump_ast(&program, &source_code));
+        check_golden(&source_path.with_extension("ast.json"), &(emit::ast_to_json(&program).to_pretty() + "\n"));
+    }
+}
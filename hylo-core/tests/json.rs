@@ -0,0 +1,28 @@
+use hylo_core::json::Json;
+
+#[test]
+fn compact_and_pretty_output() {
+    let value = Json::object(vec![
+        ("name", Json::from("a \"quoted\"\nline\u{1}")),
+        ("items", Json::from(vec![1usize, 2])),
+        ("empty", Json::Array(Vec::new())),
+        ("none", Json::from(None::<&str>)),
+    ]);
+
+    assert_eq!(
+        value.to_compact(),
+        r#"{"name":"a \"quoted\"\nline\u0001","items":[1,2],"empty":[],"none":null}"#
+    );
+    assert_eq!(
+        value.to_pretty(),
+        "{\n  \"name\": \"a \\\"quoted\\\"\\nline\\u0001\",\n  \"items\": [\n    1,\n    2\n  ],\n  \"empty\": [],\n  \"none\": null\n}"
+    );
+}
+
+#[test]
+fn numbers_stay_exact() {
+    assert_eq!(Json::from(u128::MAX).to_compact(), "340282366920938463463374607431768211455");
+    assert_eq!(Json::from(2.5f64).to_compact(), "2.5");
+    assert_eq!(Json::from(3.0f64).to_compact(), "3.0");
+    assert_eq!(Json::from(f64::INFINITY), Json::Null);
+}
@@ -0,0 +1,280 @@
+use hylo_core::lexer::lexer::Lexer;
+use hylo_core::lexer::source_code::SourceCodeContainer;
+use hylo_core::hylo_error::{Diagnostics, Error, ErrorKind};
+use hylo_core::lexer::tokens::{DocStyle, Keyword, NumberSuffix, Operator, Token};
+
+fn lex_errors(source: &str) -> Vec<Error> {
+    let source_code = SourceCodeContainer::from(String::from(source));
+    let mut diagnostics = Diagnostics::new();
+    Lexer::parse(&source_code, "test.hy", &mut diagnostics);
+    assert!(diagnostics.has_errors(), "lexing should fail");
+    diagnostics.into_errors()
+}
+
+fn lex_err(source: &str) -> Error {
+    let mut errors = lex_errors(source);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    errors.remove(0)
+}
+
+fn word(w: &str) -> Token {
+    Token::Word(String::from(w))
+}
+
+fn lex(source: &str) -> Vec<Token> {
+    let source_code = SourceCodeContainer::from(String::from(source));
+    let mut diagnostics = Diagnostics::new();
+    let tokens = Lexer::parse(&source_code, "test.hy", &mut diagnostics);
+    assert!(!diagnostics.has_errors(), "lexing failed: {:?}", diagnostics);
+    tokens
+        .into_iter()
+        .map(|t_con| t_con.token)
+        .collect()
+}
+
+#[test]
+fn keywords_are_reserved() {
+    let keywords = [
+        Keyword::Let, Keyword::Const, Keyword::Fn, Keyword::Return, Keyword::If,
+        Keyword::Else, Keyword::While, Keyword::Loop, Keyword::Break, Keyword::Continue,
+    ];
+    for keyword in keywords {
+        assert_eq!(lex(keyword.as_symbol()), vec![Token::Keyword(keyword)]);
+    }
+}
+
+#[test]
+fn words_containing_keywords_are_identifiers() {
+    assert_eq!(
+        lex("letter iffy _fn return_value"),
+        vec![
+            Token::Word(String::from("letter")),
+            Token::Word(String::from("iffy")),
+            Token::Word(String::from("_fn")),
+            Token::Word(String::from("return_value")),
+        ]
+    );
+}
+
+#[test]
+fn line_comments_are_skipped() {
+    assert_eq!(lex("a // b c\nd"), vec![word("a"), word("d")]);
+    assert_eq!(lex("// only a comment"), vec![]);
+    assert_eq!(lex("a / b // c"), vec![word("a"), Token::Operator(Operator::Divide), word("b")]);
+    assert_eq!(lex("//// not a doc comment\na"), vec![word("a")]);
+}
+
+#[test]
+fn block_comments_are_skipped() {
+    assert_eq!(lex("a /* b */ c"), vec![word("a"), word("c")]);
+    assert_eq!(lex("a /* multi\nline */ c"), vec![word("a"), word("c")]);
+    assert_eq!(lex("a /* outer /* inner */ still outer */ c"), vec![word("a"), word("c")]);
+    assert_eq!(lex("a/**/b"), vec![word("a"), word("b")]);
+}
+
+#[test]
+fn comments_right_after_an_operator() {
+    assert_eq!(lex("a +// c\nb"), vec![word("a"), Token::Operator(Operator::Plus), word("b")]);
+    assert_eq!(lex("x =/* c */ y"), vec![word("x"), Token::Operator(Operator::Equals), word("y")]);
+    assert_eq!(lex("a <=/**/b"), vec![word("a"), Token::Operator(Operator::LessThanOrEqual), word("b")]);
+    assert_eq!(
+        lex("a -/// doc\nb"),
+        vec![word("a"), Token::Operator(Operator::Minus), Token::DocComment(DocStyle::Outer, String::from(" doc")), word("b")]
+    );
+}
+
+#[test]
+fn doc_comments_are_tokens() {
+    assert_eq!(
+        lex("//! File docs\n/// Item docs\nlet"),
+        vec![
+            Token::DocComment(DocStyle::Inner, String::from(" File docs")),
+            Token::DocComment(DocStyle::Outer, String::from(" Item docs")),
+            Token::Keyword(Keyword::Let),
+        ]
+    );
+    assert_eq!(lex("///"), vec![Token::DocComment(DocStyle::Outer, String::new())]);
+}
+
+#[test]
+fn unterminated_block_comment_points_at_the_opening() {
+    let error = lex_err("a\n  /* never /* closed */");
+    assert_eq!(error.kind, ErrorKind::UnterminatedBlockComment);
+    assert_eq!((error.span.start, error.span.stop), (4, 5));
+}
+
+fn string(s: &str) -> Token {
+    Token::String(String::from(s))
+}
+
+#[test]
+fn simple_escapes() {
+    assert_eq!(lex(r#""a\nb\tc\rd""#), vec![string("a\nb\tc\rd")]);
+    assert_eq!(lex(r#""\\ \" \' \0""#), vec![string("\\ \" ' \0")]);
+    assert_eq!(lex(r#"'it\'s'"#), vec![string("it's")]);
+    assert_eq!(lex(r#""""#), vec![string("")]);
+}
+
+#[test]
+fn escaped_quote_does_not_end_the_string() {
+    assert_eq!(lex(r#""a\"b" c"#), vec![string("a\"b"), word("c")]);
+}
+
+#[test]
+fn hex_and_unicode_escapes() {
+    assert_eq!(lex(r#""\x41\x7f\x0A""#), vec![string("A\x7f\n")]);
+    assert_eq!(lex(r#""\u{48}\u{e9}\u{1F600}\u{10FFFF}""#), vec![string("Hé😀\u{10FFFF}")]);
+}
+
+#[test]
+fn invalid_escape_spans_cover_the_escape() {
+    let cases = [
+        (r#""ab\qc""#, (3, 4)),
+        (r#""\x4""#, (1, 3)),
+        (r#""\xZZ""#, (1, 2)),
+        (r#""\x80""#, (1, 4)),
+        (r#""\u41""#, (1, 2)),
+        (r#""\u{}""#, (1, 4)),
+        (r#""\u{1234567}""#, (1, 11)),
+        (r#""\u{D800}""#, (1, 8)),
+        (r#""\u{110000}""#, (1, 10)),
+        (r#""\u{41""#, (1, 5)),
+    ];
+    for (source, span) in cases {
+        let error = lex_err(source);
+        assert_eq!(error.kind, ErrorKind::InvalidEscape, "{}", source);
+        assert_eq!((error.span.start, error.span.stop), span, "{}", source);
+    }
+}
+
+#[test]
+fn every_bad_escape_is_reported() {
+    let errors = lex_errors(r#"a = "\q \x \u{zz}"; b = "\w";"#);
+    let spans: Vec<(usize, usize)> = errors.iter().map(|e| (e.span.start, e.span.stop)).collect();
+    assert_eq!(spans, vec![(5, 6), (8, 9), (11, 13), (25, 26)]);
+    assert!(errors.iter().all(|e| e.kind == ErrorKind::InvalidEscape));
+}
+
+#[test]
+fn unterminated_string_is_still_reported() {
+    let errors = lex_errors(r#""\q"#);
+    let kinds: Vec<ErrorKind> = errors.into_iter().map(|e| e.kind).collect();
+    assert_eq!(kinds, vec![ErrorKind::InvalidEscape, ErrorKind::StringNotTerminated]);
+}
+
+fn numbers(source: &str) -> Vec<(f64, Option<NumberSuffix>)> {
+    lex(source)
+        .into_iter()
+        .map(|token| match token {
+            Token::Int(int) => (int.value as f64, int.suffix),
+            Token::Float(float) => (float.value, float.suffix),
+            other => panic!("expected a number, got {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn decimal_numbers() {
+    assert_eq!(numbers("0 42 1_000_000"), vec![(0.0, None), (42.0, None), (1e6, None)]);
+    assert_eq!(numbers("1.5 0.25 1_0.5_0"), vec![(1.5, None), (0.25, None), (10.5, None)]);
+    assert_eq!(numbers("1e3 2.5E-2 1e+2 1e1_0"), vec![(1e3, None), (2.5e-2, None), (1e2, None), (1e10, None)]);
+}
+
+#[test]
+fn radix_numbers() {
+    assert_eq!(numbers("0xFF 0x7fff_FFFF"), vec![(255.0, None), (i32::MAX as f64, None)]);
+    assert_eq!(numbers("0o777 0b1010_1010"), vec![(511.0, None), (170.0, None)]);
+    assert_eq!(numbers("0x1f32"), vec![(7986.0, None)]);
+}
+
+#[test]
+fn number_suffixes() {
+    assert_eq!(numbers("10i64 255u8 0xFFu16"), vec![
+        (10.0, Some(NumberSuffix::I64)),
+        (255.0, Some(NumberSuffix::U8)),
+        (255.0, Some(NumberSuffix::U16)),
+    ]);
+    assert_eq!(numbers("2.5f64 1e3f32 10f32"), vec![
+        (2.5, Some(NumberSuffix::F64)),
+        (1e3, Some(NumberSuffix::F32)),
+        (10.0, Some(NumberSuffix::F32)),
+    ]);
+}
+
+#[test]
+fn numbers_keep_their_source_text_and_full_width() {
+    match &lex("0xFFFF_FFFF_FFFF_FFFFu64")[0] {
+        Token::Int(int) => {
+            assert_eq!(int.text, "0xFFFF_FFFF_FFFF_FFFFu64");
+            assert_eq!(int.value, u64::MAX as u128);
+        }
+        other => panic!("expected an integer, got {:?}", other),
+    }
+    match &lex("340282366920938463463374607431768211455")[0] {
+        Token::Int(int) => assert_eq!(int.value, u128::MAX),
+        other => panic!("expected an integer, got {:?}", other),
+    }
+    match &lex("1.000000000000001")[0] {
+        Token::Float(float) => assert_eq!(float.value, 1.000000000000001),
+        other => panic!("expected a float, got {:?}", other),
+    }
+}
+
+#[test]
+fn dot_after_integer_is_member_access() {
+    let tokens = lex("1.max");
+    assert!(matches!(&tokens[0], Token::Int(int) if int.value == 1));
+    assert_eq!(tokens[1..], [Token::Operator(Operator::Dot), word("max")]);
+}
+
+#[test]
+fn malformed_numbers_have_exact_spans() {
+    let cases = [
+        ("1.2.3", (3, 3)),
+        ("0b102", (4, 4)),
+        ("0o78", (3, 3)),
+        ("0x", (0, 1)),
+        ("0b__", (0, 3)),
+        ("1e+", (1, 2)),
+        ("1.5e_", (3, 4)),
+        ("12abc", (2, 4)),
+        ("1.5i32", (3, 5)),
+        ("0b1f32", (3, 5)),
+    ];
+    for (source, span) in cases {
+        let error = lex_err(source);
+        assert_eq!(error.kind, ErrorKind::SyntaxError, "{}", source);
+        assert_eq!((error.span.start, error.span.stop), span, "{}", source);
+    }
+}
+
+#[test]
+fn integer_literal_too_large() {
+    for source in ["340282366920938463463374607431768211456", "0x1_0000_0000_0000_0000_0000_0000_0000_0000"] {
+        let error = lex_err(source);
+        assert_eq!(error.kind, ErrorKind::IntegerLiteralTooLarge, "{}", source);
+        assert_eq!((error.span.start, error.span.stop), (0, source.len() - 1), "{}", source);
+    }
+}
+
+#[test]
+fn lexer_reports_every_bad_token() {
+    let errors = lex_errors("let a = @;\nlet b = 0b102 + 1;\nlet c = #;\na & b;");
+    let spans: Vec<(usize, usize)> = errors.iter().map(|e| (e.span.start, e.span.stop)).collect();
+    assert_eq!(spans, vec![(8, 8), (23, 23), (38, 38), (43, 43)]);
+}
+
+#[test]
+fn operator_pairs_split_when_they_are_not_one_operator() {
+    assert_eq!(lex("x=-y"), vec![word("x"), Token::Operator(Operator::Equals), Token::Operator(Operator::Minus), word("y")]);
+    assert_eq!(lex("a+-b"), vec![word("a"), Token::Operator(Operator::Plus), Token::Operator(Operator::Minus), word("b")]);
+    assert_eq!(lex("x=!y"), vec![word("x"), Token::Operator(Operator::Equals), Token::Operator(Operator::Exclamation), word("y")]);
+    // Known pairs still win
+    assert_eq!(lex("a!=b"), vec![word("a"), Token::Operator(Operator::IsNotEqual), word("b")]);
+    assert_eq!(lex("a<=-b"), vec![word("a"), Token::Operator(Operator::LessThanOrEqual), Token::Operator(Operator::Minus), word("b")]);
+
+    // Neither half of an unknown pair is an operator on its own
+    let errors = lex_errors("a &| b");
+    let spans: Vec<(usize, usize)> = errors.iter().map(|e| (e.span.start, e.span.stop)).collect();
+    assert_eq!(spans, vec![(2, 2), (3, 3)]);
+}
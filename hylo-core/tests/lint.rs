@@ -0,0 +1,95 @@
+use hylo_core::compiler::HyloCompiler;
+use hylo_core::lexer::source_code::SourceCodeContainer;
+use hylo_core::hylo_error::{Diagnostics, ErrorKind, Severity};
+use hylo_core::lint::{LintLevel, LintLevels};
+
+fn diagnostics(source: &str, lint_levels: LintLevels) -> Diagnostics {
+    let source_code = SourceCodeContainer::from(String::from(source));
+    match HyloCompiler::new().with_lint_levels(lint_levels).compile(&source_code, "test.hy") {
+        Ok(compilation) => compilation.warnings,
+        Err(diagnostics) => diagnostics,
+    }
+}
+
+fn codes(diagnostics: &Diagnostics) -> Vec<(&'static str, Severity)> {
+    diagnostics.iter().map(|e| (e.kind.code(), e.severity)).collect()
+}
+
+const SOURCE: &str = "\
+fn f() {
+    return 1;
+    let a = 2;
+}
+while (true) {
+    break;
+}
+";
+
+#[test]
+fn warnings_do_not_fail_compilation() {
+    let warnings = diagnostics(SOURCE, LintLevels::new());
+    assert_eq!(codes(&warnings), vec![
+        ("W0001", Severity::Warning),
+        ("W0002", Severity::Warning),
+        ("W0003", Severity::Warning),
+    ]);
+    assert!(!warnings.has_errors());
+    assert_eq!(warnings.exit_code(), 0);
+    assert_eq!(warnings.pretty(None, Some(0)), "⚠️ Found 3 warnings, 3 more not shown\n");
+}
+
+#[test]
+fn unreachable_code_skips_function_declarations() {
+    let source = "loop {\n    break;\n    fn helper() {}\n}\nfn g() {\n    return;\n    fn h() {}\n    g();\n}\n";
+    let warnings = diagnostics(source, LintLevels::new());
+    assert_eq!(codes(&warnings), vec![("W0001", Severity::Warning)]);
+    assert_eq!(warnings.iter().next().unwrap().span.start, source.find("g();").unwrap());
+}
+
+#[test]
+fn levels_allow_and_deny_warnings() {
+    let mut lint_levels = LintLevels::new();
+    lint_levels.set(ErrorKind::UnreachableCode, LintLevel::Deny);
+    lint_levels.set(ErrorKind::UnnecessaryParentheses, LintLevel::Allow);
+    let diagnostics = diagnostics(SOURCE, lint_levels);
+    assert_eq!(codes(&diagnostics), vec![("W0001", Severity::Error), ("W0002", Severity::Warning)]);
+    assert_eq!(diagnostics.exit_code(), 1);
+    assert_eq!(diagnostics.pretty(None, Some(0)), "❌ Found 1 error and 1 warning, 2 more not shown\n");
+}
+
+#[test]
+fn deny_warnings_keeps_explicit_levels() {
+    let mut lint_levels = LintLevels::new();
+    lint_levels.set_deny_warnings(true);
+    lint_levels.set(ErrorKind::WhileTrue, LintLevel::Warn);
+    let diagnostics = diagnostics(SOURCE, lint_levels);
+    assert_eq!(codes(&diagnostics), vec![
+        ("W0001", Severity::Error),
+        ("W0002", Severity::Warning),
+        ("W0003", Severity::Error),
+    ]);
+}
+
+#[test]
+fn pragmas_allow_warnings_on_one_line() {
+    let source = "\
+// hylo: allow(W0002, unnecessary_parentheses)
+while (true) { break; }
+while true { break; } // hylo: allow(while_true)
+while true { break; }
+";
+    let mut lint_levels = LintLevels::new();
+    lint_levels.set_deny_warnings(true);
+    let diagnostics = diagnostics(source, lint_levels);
+    assert_eq!(codes(&diagnostics), vec![("W0002", Severity::Error)]);
+    assert_eq!(diagnostics.iter().next().unwrap().span.start, source.rfind("while").unwrap());
+}
+
+#[test]
+fn warnings_are_found_by_code_or_name() {
+    assert_eq!(ErrorKind::warning_from("W0002"), Some(ErrorKind::WhileTrue));
+    assert_eq!(ErrorKind::warning_from("w0002"), Some(ErrorKind::WhileTrue));
+    assert_eq!(ErrorKind::warning_from("unreachable_code"), Some(ErrorKind::UnreachableCode));
+    assert_eq!(ErrorKind::warning_from("E0001"), None);
+    assert_eq!(ErrorKind::warning_from("SyntaxError"), None);
+}
@@ -0,0 +1,80 @@
+use hylo_core::compiler::HyloCompiler;
+use hylo_core::lexer::source_code::SourceCodeContainer;
+use hylo_core::hylo_error::{output, Diagnostics, ErrorFormat, Theme};
+
+fn diagnostics(source_code: &SourceCodeContainer, file_name: &str) -> Diagnostics {
+    match HyloCompiler::new().compile(source_code, file_name) {
+        Ok(compilation) => compilation.warnings,
+        Err(diagnostics) => diagnostics,
+    }
+}
+
+const SOURCE: &str = "let s = \"é\";\nlet total = add(first,\n    second;\nwhile true { break; }\n";
+
+#[test]
+fn json_has_one_object_per_line() {
+    let source_code = SourceCodeContainer::from(String::from(SOURCE));
+    let diagnostics = diagnostics(&source_code, "test.hy");
+    let json = output::render(ErrorFormat::Json, &[(&diagnostics, &source_code)], Some(0), &Theme::default());
+
+    // Byte offsets count the two bytes of `é`, columns count it once
+    let lines: Vec<&str> = json.lines().collect();
+    assert_eq!(lines, vec![
+        concat!(
+            r#"{"code":"E0001","kind":"SyntaxError","severity":"error","#,
+            r#""message":"Expected closing ')' of the function call","file":"test.hy","#,
+            r#""span":{"line_start":3,"column_start":11,"line_end":3,"column_end":12,"byte_start":47,"byte_end":48},"#,
+            r#""labels":[{"style":"secondary","message":"The call is opened here","file":"test.hy","#,
+            r#""span":{"line_start":2,"column_start":16,"line_end":2,"column_end":17,"byte_start":29,"byte_end":30}}],"#,
+            r#""notes":[],"suggestions":[{"message":"Add a closing ')' after the last argument","#,
+            r#""applicability":"machine-applicable","edits":[{"span":{"line_start":3,"column_start":11,"#,
+            r#""line_end":3,"column_end":11,"byte_start":47,"byte_end":47},"replacement":")"}]}]}"#,
+        ),
+        concat!(
+            r#"{"code":"W0002","kind":"WhileTrue","severity":"warning","#,
+            r#""message":"Infinite loop written as `while true`","file":"test.hy","#,
+            r#""span":{"line_start":4,"column_start":1,"line_end":4,"column_end":11,"byte_start":49,"byte_end":59},"#,
+            r#""labels":[],"notes":[],"suggestions":[{"message":"Use `loop` for a loop that only ends with `break`","#,
+            r#""applicability":"machine-applicable","edits":[{"span":{"line_start":4,"column_start":1,"#,
+            r#""line_end":4,"column_end":11,"byte_start":49,"byte_end":59},"replacement":"loop"}]}]}"#,
+        ),
+    ]);
+    assert_eq!(&SOURCE.as_bytes()[49..59], b"while true");
+}
+
+#[test]
+fn short_has_one_line_per_diagnostic() {
+    let source_code = SourceCodeContainer::from(String::from(SOURCE));
+    let diagnostics = diagnostics(&source_code, "test.hy");
+    assert_eq!(output::render(ErrorFormat::Short, &[(&diagnostics, &source_code)], None, &Theme::default()), "\
+test.hy:3:11: error[E0001]: Expected closing ')' of the function call
+test.hy:4:1: warning[W0002]: Infinite loop written as `while true`
+❌ Found 1 error and 1 warning
+");
+}
+
+#[test]
+fn sarif_log_covers_every_file() {
+    let first = SourceCodeContainer::from(String::from(SOURCE));
+    let second = SourceCodeContainer::from(String::from("while true {}\n"));
+    let (first_diagnostics, second_diagnostics) = (diagnostics(&first, "src/a b.hy"), diagnostics(&second, "/abs/b.hy"));
+    let sarif = output::to_sarif(&[(&first_diagnostics, &first), (&second_diagnostics, &second)]).to_compact();
+
+    assert!(sarif.starts_with(r#"{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","version":"2.1.0","runs":[{"tool":{"driver":{"name":"hylo""#));
+    // Rules are shared between files, results point at them by index
+    assert_eq!(sarif.matches(r#""id":"#).count(), 2);
+    assert_eq!(sarif.matches(r#""ruleId":"W0002","ruleIndex":1"#).count(), 2);
+    assert!(sarif.contains(r#""uri":"src/a%20b.hy""#));
+    assert!(sarif.contains(r#""uri":"file:///abs/b.hy""#));
+    assert!(sarif.contains(r#""region":{"startLine":3,"startColumn":11,"endLine":3,"endColumn":12,"byteOffset":47,"byteLength":1}"#));
+    assert!(sarif.contains(r#""message":{"text":"The call is opened here"}"#));
+    assert!(sarif.contains(concat!(
+        r#""fixes":[{"description":{"text":"Add a closing ')' after the last argument"},"#,
+        r#""artifactChanges":[{"artifactLocation":{"uri":"src/a%20b.hy"},"replacements":[{"deletedRegion":"#,
+        r#"{"startLine":3,"startColumn":11,"endLine":3,"endColumn":11,"byteOffset":47,"byteLength":0},"#,
+        r#""insertedContent":{"text":")"}}]}]}]"#,
+    )));
+
+    let empty = output::render(ErrorFormat::Sarif, &[], None, &Theme::default());
+    assert!(empty.contains(r#""results": []"#));
+}
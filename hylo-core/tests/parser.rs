@@ -0,0 +1,509 @@
+use hylo_core::lexer::lexer::Lexer;
+use hylo_core::lexer::source_code::SourceCodeContainer;
+use hylo_core::hylo_error::{Diagnostics, Error, ErrorKind, Severity};
+use hylo_core::parser::components::{BinaryOp, Block, DeclKind, ElseBody, Expr, Literal, Program, Stmt, UnaryOp};
+use hylo_core::parser::parser::Parser;
+
+fn try_parse(source: &str) -> Result<Program, Vec<Error>> {
+    let source_code = SourceCodeContainer::from(String::from(source));
+    let mut diagnostics = Diagnostics::new();
+    let tokens = Lexer::parse(&source_code, "test.hy", &mut diagnostics);
+    assert!(!diagnostics.has_errors(), "lexing failed: {:?}", diagnostics);
+
+    let program = Parser::new(tokens, "test.hy", &mut diagnostics).parse_program();
+    // Warnings are covered by tests/lint.rs
+    if diagnostics.has_errors() {
+        return Err(diagnostics.into_errors().into_iter().filter(|e| e.severity == Severity::Error).collect());
+    }
+    Ok(program)
+}
+
+fn parse(source: &str) -> Program {
+    try_parse(source).expect("parsing failed")
+}
+
+// Renders an expression as an S-expression so tree shapes are easy to compare
+fn sexpr(expr: &Expr) -> String {
+    match expr {
+        Expr::Literal(Literal::Int(v, _)) => v.text.clone(),
+        Expr::Literal(Literal::Float(v, _)) => v.text.clone(),
+        Expr::Literal(Literal::String(v, _)) => format!("{:?}", v),
+        Expr::Literal(Literal::Bool(v, _)) => v.to_string(),
+        Expr::Literal(Literal::Word(v, _)) => v.clone(),
+        Expr::Unary { op, expr } => {
+            let op = match op {
+                UnaryOp::Not(_) => "!",
+                UnaryOp::Negative(_) => "-",
+            };
+            format!("({} {})", op, sexpr(expr))
+        }
+        Expr::Binary { left, op, right } => {
+            let op = match op {
+                BinaryOp::Add(_) => "+",
+                BinaryOp::Sub(_) => "-",
+                BinaryOp::Mul(_) => "*",
+                BinaryOp::Div(_) => "/",
+                BinaryOp::Greater(_) => ">",
+                BinaryOp::Less(_) => "<",
+                BinaryOp::GreaterEqual(_) => ">=",
+                BinaryOp::LessEqual(_) => "<=",
+                BinaryOp::IsEqual(_) => "==",
+                BinaryOp::IsNotEqual(_) => "!=",
+                BinaryOp::And(_) => "&&",
+                BinaryOp::Or(_) => "||",
+            };
+            format!("({} {} {})", op, sexpr(left), sexpr(right))
+        }
+        Expr::Call { callee, args, .. } => {
+            let mut out = format!("(call {}", sexpr(callee));
+            for arg in args {
+                out.push(' ');
+                out.push_str(&sexpr(arg));
+            }
+            out.push(')');
+            out
+        }
+        Expr::Member { obj, member, .. } => format!("(. {} {})", sexpr(obj), sexpr(member)),
+        Expr::Index { obj, index, .. } => format!("([] {} {})", sexpr(obj), sexpr(index)),
+        Expr::Grouping { expr, .. } => format!("(group {})", sexpr(expr)),
+    }
+}
+
+fn sstmt(stmt: &Stmt) -> String {
+    match stmt {
+        Stmt::ExprStmt { expr, .. } => sexpr(expr),
+        Stmt::Let { kind, name, ty, init, .. } => {
+            let keyword = match kind {
+                DeclKind::Let => "let",
+                DeclKind::Const => "const",
+            };
+            match ty {
+                Some(annotation) => format!("({} {}: {} {})", keyword, name.name, annotation.ty.name, sexpr(init)),
+                None => format!("({} {} {})", keyword, name.name, sexpr(init)),
+            }
+        }
+        Stmt::Assign { target, value, .. } => format!("(= {} {})", sexpr(target), sexpr(value)),
+        Stmt::Block(block) => sblock(block),
+        Stmt::If { condition, then_block, else_branch, .. } => match else_branch {
+            Some(else_branch) => {
+                let else_body = match &else_branch.body {
+                    ElseBody::Block(block) => sblock(block),
+                    ElseBody::If(stmt) => sstmt(stmt),
+                };
+                format!("(if {} {} {})", sexpr(condition), sblock(then_block), else_body)
+            }
+            None => format!("(if {} {})", sexpr(condition), sblock(then_block)),
+        },
+        Stmt::While { condition, body, .. } => format!("(while {} {})", sexpr(condition), sblock(body)),
+        Stmt::Loop { body, .. } => format!("(loop {})", sblock(body)),
+        Stmt::Break { .. } => String::from("break"),
+        Stmt::Continue { .. } => String::from("continue"),
+        Stmt::FnDecl(fn_decl) => {
+            let params: Vec<String> = fn_decl.params.iter().map(|p| format!("{}: {}", p.name.name, p.ty.name)).collect();
+            let ret = match &fn_decl.ret {
+                Some(ret) => format!(" -> {}", ret.ty.name),
+                None => String::new(),
+            };
+            format!("(fn {}({}){} {})", fn_decl.name.name, params.join(", "), ret, sblock(&fn_decl.body))
+        }
+        Stmt::Return { value: Some(value), .. } => format!("(return {})", sexpr(value)),
+        Stmt::Return { value: None, .. } => String::from("(return)"),
+    }
+}
+
+fn sblock(block: &Block) -> String {
+    let statements: Vec<String> = block.statements.iter().map(sstmt).collect();
+    format!("{{{}}}", statements.join(" "))
+}
+
+fn stmts_of(source: &str) -> Vec<String> {
+    parse(source).statements.iter().map(sstmt).collect()
+}
+
+fn expr_of(source: &str) -> String {
+    let program = parse(source);
+    assert_eq!(program.statements.len(), 1);
+    match &program.statements[0] {
+        Stmt::ExprStmt { expr, .. } => sexpr(expr),
+        other => panic!("expected an expression statement, got {:?}", other),
+    }
+}
+
+fn first_error(source: &str) -> Error {
+    try_parse(source).unwrap_err().remove(0)
+}
+
+#[test]
+fn arithmetic_precedence() {
+    assert_eq!(expr_of("1 + 2 * 3;"), "(+ 1 (* 2 3))");
+    assert_eq!(expr_of("1 - 2 - 3;"), "(- (- 1 2) 3)");
+    assert_eq!(expr_of("-a * !b;"), "(* (- a) (! b))");
+}
+
+#[test]
+fn comparison_binds_looser_than_arithmetic() {
+    assert_eq!(expr_of("a + 1 > b * 2;"), "(> (+ a 1) (* b 2))");
+    assert_eq!(expr_of("a <= b;"), "(<= a b)");
+    assert_eq!(expr_of("a >= b;"), "(>= a b)");
+    assert_eq!(expr_of("a < b < c;"), "(< (< a b) c)");
+}
+
+#[test]
+fn equality_binds_looser_than_comparison() {
+    assert_eq!(expr_of("a < b == c > d;"), "(== (< a b) (> c d))");
+    assert_eq!(expr_of("a == b != c;"), "(!= (== a b) c)");
+}
+
+#[test]
+fn logical_operators() {
+    assert_eq!(expr_of("a && b || c && d;"), "(|| (&& a b) (&& c d))");
+    assert_eq!(expr_of("a || b || c;"), "(|| (|| a b) c)");
+    assert_eq!(expr_of("a && b && c;"), "(&& (&& a b) c)");
+    assert_eq!(expr_of("a == 1 && !b;"), "(&& (== a 1) (! b))");
+}
+
+#[test]
+fn calls_as_operands() {
+    assert_eq!(expr_of("f(a, b) == g();"), "(== (call f a b) (call g))");
+}
+
+#[test]
+fn expression_statements() {
+    let program = parse("a; b;");
+    assert_eq!(program.statements.len(), 2);
+    assert!(program.statements.iter().all(|stmt| matches!(stmt, Stmt::ExprStmt { .. })));
+    assert_eq!(stmts_of("a; b;"), vec!["a", "b"]);
+}
+
+#[test]
+fn missing_semicolon_is_an_error() {
+    let errors = try_parse("a == b").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ErrorKind::SyntaxError);
+    assert_eq!(errors[0].message, "Expected ';' at the end of the statement");
+
+    let errors = try_parse("a; b").unwrap_err();
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn grouping_overrides_precedence() {
+    assert_eq!(expr_of("(1 + 2) * 3;"), "(* (group (+ 1 2)) 3)");
+    assert_eq!(expr_of("!(a || b);"), "(! (group (|| a b)))");
+}
+
+#[test]
+fn postfix_binds_tighter_than_prefix() {
+    assert_eq!(expr_of("-a.b;"), "(- (. a b))");
+    assert_eq!(expr_of("!f(x);"), "(! (call f x))");
+    assert_eq!(expr_of("a.b.c(1) + d.e;"), "(+ (call (. (. a b) c) 1) (. d e))");
+}
+
+#[test]
+fn nested_prefix_operators() {
+    assert_eq!(expr_of("! !a;"), "(! (! a))");
+    assert_eq!(expr_of("- -a - -b;"), "(- (- (- a)) (- b))");
+}
+
+#[test]
+fn prefix_operators_without_spaces() {
+    assert_eq!(stmts_of("let x=-1;"), vec!["(let x (- 1))"]);
+    assert_eq!(stmts_of("x=!y;"), vec!["(= x (! y))"]);
+    assert_eq!(stmts_of("let y = a+-b;"), vec!["(let y (+ a (- b)))"]);
+}
+
+#[test]
+fn index_expressions() {
+    assert_eq!(expr_of("a[1] + b[i][j];"), "(+ ([] a 1) ([] ([] b i) j))");
+    assert_eq!(expr_of("a.b[0].c;"), "(. ([] (. a b) 0) c)");
+}
+
+#[test]
+fn declarations() {
+    assert_eq!(stmts_of("let x = 1 + 2;"), vec!["(let x (+ 1 2))"]);
+    assert_eq!(stmts_of("const limit: int = 10;"), vec!["(const limit: int 10)"]);
+    assert_eq!(stmts_of("let s: string = f(x); let y = s;"), vec!["(let s: string (call f x))", "(let y s)"]);
+}
+
+#[test]
+fn declaration_spans() {
+    let program = parse("let answer: int = 42;");
+    match &program.statements[0] {
+        Stmt::Let { keyword, name, ty, equals, init, semicolon, .. } => {
+            assert_eq!((keyword.start, keyword.end), (0, 2));
+            assert_eq!((name.span.start, name.span.end), (4, 9));
+            let ty = ty.as_ref().unwrap();
+            assert_eq!((ty.colon.start, ty.ty.span.start, ty.ty.span.end), (10, 12, 14));
+            assert_eq!(equals.start, 16);
+            assert_eq!((init.span().start, init.span().end), (18, 19));
+            assert_eq!(semicolon.start, 20);
+        }
+        other => panic!("expected a declaration, got {:?}", other),
+    }
+}
+
+#[test]
+fn declarations_require_an_initializer() {
+    assert_eq!(first_error("let x;").kind, ErrorKind::SyntaxError);
+    assert_eq!(first_error("let x: int;").kind, ErrorKind::SyntaxError);
+    assert_eq!(first_error("let = 3;").kind, ErrorKind::SyntaxError);
+}
+
+#[test]
+fn assignments_to_places() {
+    assert_eq!(stmts_of("x = x + 1;"), vec!["(= x (+ x 1))"]);
+    assert_eq!(stmts_of("a.b = c == d;"), vec!["(= (. a b) (== c d))"]);
+    assert_eq!(stmts_of("a[i + 1] = 0;"), vec!["(= ([] a (+ i 1)) 0)"]);
+}
+
+#[test]
+fn assignments_to_non_places_are_rejected() {
+    for source in ["f() = 1;", "1 = x;", "a + b = c;", "-a = 1;", "\"s\" = 1;"] {
+        let error = first_error(source);
+        assert_eq!(error.kind, ErrorKind::InvalidAssignmentTarget, "{}", source);
+    }
+
+    // The error points at the whole target
+    let error = first_error("f(a) = 1;");
+    assert_eq!((error.span.start, error.span.stop), (0, 3));
+}
+
+#[test]
+fn blocks() {
+    assert_eq!(stmts_of("{ let a = 1; { a; } }"), vec!["{(let a 1) {a}}"]);
+    assert_eq!(stmts_of("{}"), vec!["{}"]);
+}
+
+#[test]
+fn if_else_chains() {
+    assert_eq!(stmts_of("if a == 1 { b; }"), vec!["(if (== a 1) {b})"]);
+    assert_eq!(stmts_of("if a { b; } else { c; }"), vec!["(if a {b} {c})"]);
+    assert_eq!(
+        stmts_of("if a { b; } else if c { d; } else { e; }"),
+        vec!["(if a {b} (if c {d} {e}))"]
+    );
+}
+
+#[test]
+fn loops() {
+    assert_eq!(stmts_of("while i < 10 { i = i + 1; }"), vec!["(while (< i 10) {(= i (+ i 1))})"]);
+    assert_eq!(
+        stmts_of("loop { if done { break; } continue; }"),
+        vec!["(loop {(if done {break}) continue})"]
+    );
+}
+
+#[test]
+fn control_flow_keyword_spans() {
+    let program = parse("if a {} else {}\nwhile b { break; }");
+    match &program.statements[0] {
+        Stmt::If { keyword, else_branch, .. } => {
+            assert_eq!((keyword.start, keyword.end), (0, 1));
+            let else_keyword = else_branch.as_ref().unwrap().keyword;
+            assert_eq!((else_keyword.start, else_keyword.end), (8, 11));
+        }
+        other => panic!("expected an if statement, got {:?}", other),
+    }
+    match &program.statements[1] {
+        Stmt::While { keyword, body, .. } => {
+            assert_eq!((keyword.start, keyword.end), (16, 20));
+            match &body.statements[0] {
+                Stmt::Break { keyword, semicolon } => assert_eq!((keyword.start, semicolon.end), (26, 31)),
+                other => panic!("expected a break, got {:?}", other),
+            }
+        }
+        other => panic!("expected a while loop, got {:?}", other),
+    }
+}
+
+#[test]
+fn unclosed_block_is_an_error() {
+    let error = first_error("while a { b;");
+    assert_eq!(error.kind, ErrorKind::SyntaxError);
+    assert_eq!(error.span.start, 8);
+}
+
+#[test]
+fn loop_control_outside_loops_is_rejected() {
+    let errors = try_parse("break; if a { continue; }").unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().all(|e| e.kind == ErrorKind::LoopControlOutsideLoop));
+    assert_eq!((errors[0].span.start, errors[0].span.stop), (0, 4));
+    assert_eq!((errors[1].span.start, errors[1].span.stop), (14, 21));
+
+    assert!(try_parse("while a { if b { break; } else { continue; } }").is_ok());
+    assert!(try_parse("loop { { break; } }").is_ok());
+}
+
+#[test]
+fn function_declarations() {
+    assert_eq!(stmts_of("fn main() {}"), vec!["(fn main() {})"]);
+    assert_eq!(
+        stmts_of("fn add(a: int, b: int) -> int { return a + b; }"),
+        vec!["(fn add(a: int, b: int) -> int {(return (+ a b))})"]
+    );
+    assert_eq!(
+        stmts_of("fn log(msg: string) { print(msg); return; }"),
+        vec!["(fn log(msg: string) {(call print msg) (return)})"]
+    );
+}
+
+#[test]
+fn function_spans() {
+    let program = parse("fn add(a: int, bb: float) -> int {}");
+    match &program.statements[0] {
+        Stmt::FnDecl(fn_decl) => {
+            assert_eq!((fn_decl.keyword.start, fn_decl.keyword.end), (0, 1));
+            assert_eq!((fn_decl.name.span.start, fn_decl.name.span.end), (3, 5));
+            assert_eq!((fn_decl.lparen.start, fn_decl.rparen.start), (6, 24));
+
+            let spans: Vec<(usize, usize)> = fn_decl.params.iter().map(|p| (p.span().start, p.span().end)).collect();
+            assert_eq!(spans, vec![(7, 12), (15, 23)]);
+            assert_eq!((fn_decl.params[1].ty.span.start, fn_decl.params[1].ty.span.end), (19, 23));
+
+            let ret = fn_decl.ret.as_ref().unwrap();
+            assert_eq!((ret.arrow.start, ret.arrow.end), (26, 27));
+            assert_eq!((ret.ty.span.start, ret.ty.span.end), (29, 31));
+        }
+        other => panic!("expected a function, got {:?}", other),
+    }
+}
+
+#[test]
+fn malformed_function_declarations() {
+    assert_eq!(first_error("fn () {}").kind, ErrorKind::SyntaxError);
+    assert_eq!(first_error("fn f(a) {}").kind, ErrorKind::SyntaxError);
+    assert_eq!(first_error("fn f(a: int {}").kind, ErrorKind::SyntaxError);
+    assert_eq!(first_error("fn f() -> {}").kind, ErrorKind::SyntaxError);
+    assert_eq!(first_error("fn f();").kind, ErrorKind::SyntaxError);
+}
+
+#[test]
+fn functions_reset_the_loop_context() {
+    let errors = try_parse("loop { fn f() { break; } break; }").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ErrorKind::LoopControlOutsideLoop);
+    assert_eq!(errors[0].span.start, 16);
+}
+
+#[test]
+fn keywords_cannot_be_used_as_names() {
+    for source in ["let fn = 1;", "fn while() {}", "fn f(loop: int) {}", "a.if;", "else;", "x = return;"] {
+        let error = first_error(source);
+        assert_eq!(error.kind, ErrorKind::SyntaxError, "{}", source);
+        assert!(error.details.notes.iter().any(|note| note.contains("reserved keyword")), "{}", source);
+    }
+}
+
+#[test]
+fn doc_comments_attach_to_declarations() {
+    let program = parse("//! A module\n/// Adds\n/// numbers\nfn add() {}\n/// The answer\nconst x = 42;");
+    let docs: Vec<&str> = program.docs.iter().map(|d| d.text.as_str()).collect();
+    assert_eq!(docs, vec![" A module"]);
+
+    match &program.statements[0] {
+        Stmt::FnDecl(fn_decl) => {
+            let docs: Vec<&str> = fn_decl.docs.iter().map(|d| d.text.as_str()).collect();
+            assert_eq!(docs, vec![" Adds", " numbers"]);
+        }
+        other => panic!("expected a function, got {:?}", other),
+    }
+    match &program.statements[1] {
+        Stmt::Let { docs, .. } => assert_eq!(docs[0].text, " The answer"),
+        other => panic!("expected a declaration, got {:?}", other),
+    }
+}
+
+#[test]
+fn comments_are_ignored_by_the_parser() {
+    assert_eq!(stmts_of("let /* inline */ x = 1; // trailing"), vec!["(let x 1)"]);
+    assert_eq!(stmts_of("let z = 1 +// c\n2;\nz =/* c */ 3;"), vec!["(let z (+ 1 2))", "(= z 3)"]);
+}
+
+#[test]
+fn misplaced_doc_comments_are_rejected() {
+    assert_eq!(first_error("/// dangling\nx = 1;").kind, ErrorKind::SyntaxError);
+    assert_eq!(first_error("fn f() { let a = 1; /// nothing follows\n}").kind, ErrorKind::SyntaxError);
+    assert_eq!(first_error("let a = 1;\n//! too late").kind, ErrorKind::SyntaxError);
+}
+
+#[test]
+fn literals_fit_their_suffix() {
+    assert!(try_parse("let a = 255u8; let b = -128i8; let c = -(128i8); let d = 9223372036854775807;").is_ok());
+    assert!(try_parse("let e = 18446744073709551615u64; let f = 3.4e38f32; let g = 1e308;").is_ok());
+}
+
+#[test]
+fn literals_out_of_range_point_at_the_literal() {
+    let errors = try_parse("let a = 256u8;\nlet b = -129i8;\nlet c = 128i8;\nf(9223372036854775808, 1e39f32, 1e309);").unwrap_err();
+    assert!(errors.iter().all(|e| e.kind == ErrorKind::LiteralOutOfRange));
+    let spans: Vec<(usize, usize)> = errors.iter().map(|e| (e.span.start, e.span.stop)).collect();
+    assert_eq!(spans, vec![(8, 12), (24, 28), (39, 43), (48, 66), (69, 75), (78, 82)]);
+    assert_eq!(errors[0].message, "Literal out of range for `u8`");
+    assert_eq!(errors[3].message, "Literal out of range for `i64`");
+}
+
+#[test]
+fn parser_recovers_at_statement_boundaries() {
+    let errors = try_parse("let a = 1 +;\nlet = 2;\nx = 3;\nfn f( { y; }\nz = ;").unwrap_err();
+    let lines: Vec<usize> = errors.iter().map(|e| e.span.start).collect();
+    assert_eq!(errors.len(), 4, "{:?}", errors);
+    // One error per broken statement, in source order
+    assert!(lines.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn parser_recovers_inside_blocks() {
+    let source_code = SourceCodeContainer::from(String::from("while x { let = 1; y = 2; }\nbreak;"));
+    let mut diagnostics = Diagnostics::new();
+    let tokens = Lexer::parse(&source_code, "test.hy", &mut diagnostics);
+    let program = Parser::new(tokens, "test.hy", &mut diagnostics).parse_program();
+
+    // The valid statements are kept and later checks still run on them
+    let errors: Vec<ErrorKind> = diagnostics.iter().map(|e| e.kind.clone()).collect();
+    assert_eq!(errors, vec![ErrorKind::SyntaxError, ErrorKind::LoopControlOutsideLoop]);
+    assert_eq!(program.statements.len(), 2);
+    match &program.statements[0] {
+        Stmt::While { body, .. } => assert_eq!(body.statements.len(), 1),
+        other => panic!("expected a while loop, got {:?}", other),
+    }
+}
+
+#[test]
+fn recovery_stops_at_the_semicolon_of_the_broken_statement() {
+    // The statements after the broken ones don't start with a keyword
+    let errors = try_parse("let a = 1 + ;\nbar(1, 2;\nlet b = ;\nc = (1;\n").unwrap_err();
+    let starts: Vec<usize> = errors.iter().map(|e| e.span.start).collect();
+    assert_eq!(starts, vec![12, 22, 32, 38], "{:?}", errors);
+    assert_eq!(errors[0].message, "Expected an expression, found `;`");
+    assert_eq!(errors[1].details.labels[0].message, "The call is opened here");
+    assert_eq!(errors[3].message, "Expected closing ')' of the parenthesized expression");
+}
+
+#[test]
+fn unclosed_call_points_at_both_ends() {
+    let error = first_error("f(a, b;");
+    assert_eq!((error.span.start, error.span.stop), (6, 6));
+    assert_eq!(error.details.labels.len(), 1);
+    assert_eq!((error.details.labels[0].span.start, error.details.labels[0].span.stop), (1, 1));
+    assert_eq!(error.details.labels[0].message, "The call is opened here");
+}
+
+#[test]
+fn diagnostics_are_in_source_order() {
+    let source = "let a = 1;\nlet b = ;\nwhile true { }\nlet c = 300u8;\nbreak;\n";
+    let source_code = SourceCodeContainer::from(String::from(source));
+    let mut diagnostics = Diagnostics::new();
+    let tokens = Lexer::parse(&source_code, "test.hy", &mut diagnostics);
+    Parser::new(tokens, "test.hy", &mut diagnostics).parse_program();
+
+    let lines: Vec<(usize, ErrorKind)> = diagnostics.iter()
+        .map(|e| (source_code.get_line_and_column(e.span.start).0, e.kind.clone()))
+        .collect();
+    assert_eq!(lines, vec![
+        (2, ErrorKind::SyntaxError),
+        (3, ErrorKind::WhileTrue),
+        (4, ErrorKind::LiteralOutOfRange),
+        (5, ErrorKind::LoopControlOutsideLoop),
+    ]);
+}
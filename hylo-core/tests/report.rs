@@ -0,0 +1,122 @@
+use hylo_core::lexer::source_code::SourceCodeContainer;
+use hylo_core::hylo_error::{Error, ErrorKind, Glyphs, LabelStyle, Span, Theme};
+
+fn source(text: &str) -> SourceCodeContainer {
+    SourceCodeContainer::from(String::from(text))
+}
+
+fn syntax_error(start: usize, stop: usize, msg: &str) -> Error {
+    Error::new(ErrorKind::SyntaxError, Span { start, stop }, Some("test.hy")).add_msg(msg)
+}
+
+#[test]
+fn single_label() {
+    let error = syntax_error(8, 8, "Unexpected '@'").add_note("Remove it");
+    assert_eq!(error.pretty(Some(&source("let x = @;"))), "\
+❌ ERROR [E0001]: SyntaxError
+   --> test.hy:1:9
+    |
+  1 | let x = @;
+    |         ^ Unexpected '@'
+💡 Hint: Remove it
+");
+}
+
+#[test]
+fn secondary_labels_on_other_lines() {
+    let code = source("let a = f(1,\n    2;\nlet b = 3;\n\n\n\n\n\n\n\nlet c = 4;\n");
+    let error = syntax_error(18, 18, "Expected ')'")
+        .add_label(Span { start: 9, stop: 9 }, "Opened here")
+        .add_label(Span { start: 46, stop: 46 }, "Far away");
+    assert_eq!(error.details.labels[0].style, LabelStyle::Secondary);
+    assert_eq!(error.pretty(Some(&code)), "\
+❌ ERROR [E0001]: SyntaxError
+   --> test.hy:2:6
+    |
+  1 | let a = f(1,
+    |          - Opened here
+  2 |     2;
+    |      ^ Expected ')'
+...
+ 11 | let c = 4;
+    |         - Far away
+");
+}
+
+#[test]
+fn labels_on_one_line_are_ordered_by_column() {
+    let error = syntax_error(15, 15, "Expected ']'").add_label(Span { start: 13, stop: 13 }, "Opened here");
+    assert_eq!(error.pretty(Some(&source("    let x = a[0;"))), "\
+❌ ERROR [E0001]: SyntaxError
+   --> test.hy:1:16
+    |
+  1 | let x = a[0;
+    |          - Opened here
+    |            ^ Expected ']'
+");
+}
+
+#[test]
+fn spans_over_several_lines() {
+    let code = source("fn f() {\n    return;\n    a();\n    b();\n}");
+    let error = syntax_error(25, 37, "Never run");
+    assert_eq!(error.pretty(Some(&code)), "\
+❌ ERROR [E0001]: SyntaxError
+   --> test.hy:3:5
+    |
+  3 | a();
+    | ^^^^
+  4 | b();
+    | ^^^^ Never run
+");
+}
+
+#[test]
+fn labels_in_other_files_are_listed() {
+    let error = syntax_error(0, 2, "Redefined")
+        .add_label_in("other.hy", Span { start: 4, stop: 6 }, "First defined here");
+    assert_eq!(error.pretty(Some(&source("abc"))), "\
+❌ ERROR [E0001]: SyntaxError
+   --> test.hy:1:1
+    |
+  1 | abc
+    | ^^^ Redefined
+   ::: other.hy: First defined here
+");
+    assert_eq!(error.pretty(None), "\
+❌ ERROR [E0001]: SyntaxError
+File: test.hy | Code: E0001
+   |
+   | ❗ Redefined
+   | - First defined here (in other.hy)
+");
+}
+
+#[test]
+fn ascii_theme_has_no_emoji() {
+    let error = syntax_error(8, 8, "Unexpected '@'").add_note("Remove it");
+    let report = error.pretty_with(Some(&source("let x = @;")), &Theme::ascii());
+    assert_eq!(report, "\
+ERROR [E0001]: SyntaxError
+   --> test.hy:1:9
+    |
+  1 | let x = @;
+    |         ^ Unexpected '@'
+hint: Remove it
+");
+    assert!(error.pretty_with(None, &Theme::ascii()).is_ascii());
+}
+
+#[test]
+fn colour_wraps_each_part_in_ansi_codes() {
+    let error = syntax_error(8, 8, "Unexpected '@'").add_label(Span { start: 4, stop: 4 }, "Here");
+    let report = error.pretty_with(Some(&source("let x = @;")), &Theme::new(Glyphs::Ascii, true));
+    assert_eq!(report, "\
+\x1b[1;31mERROR [E0001]\x1b[0m\x1b[1m: SyntaxError\x1b[0m
+\x1b[1;34m   -->\x1b[0m test.hy:1:9
+\x1b[1;34m    |\x1b[0m
+\x1b[1;34m  1 |\x1b[0m let x = @;
+\x1b[1;34m    |\x1b[0m     \x1b[1;34m- Here\x1b[0m
+\x1b[1;34m    |\x1b[0m         \x1b[1;31m^ Unexpected '@'\x1b[0m
+");
+}
@@ -0,0 +1,66 @@
+use hylo_core::lexer::source_code::SourceCodeContainer;
+
+fn source(text: &str) -> SourceCodeContainer {
+    SourceCodeContainer::from(String::from(text))
+}
+
+#[test]
+fn every_line_ends_with_a_newline() {
+    let code = source("ab\ncd");
+    assert_eq!(code.total_lines, 2);
+    assert_eq!(code.total_chars, 6);
+    let chars: String = (0..code.total_chars).filter_map(|pos| code.char_at(pos)).collect();
+    assert_eq!(chars, "ab\ncd\n");
+    assert_eq!(code.char_at(6), None);
+
+    // A trailing newline starts an empty last line
+    let code = source("ab\n");
+    assert_eq!(code.total_lines, 2);
+    assert_eq!(code.line_span(1), (3, 3));
+    assert_eq!(code.line(1), &[] as &[char]);
+
+    let code = source("");
+    assert_eq!((code.total_lines, code.total_chars), (1, 1));
+    assert_eq!(code.get_line_and_column(0), (1, 1));
+}
+
+#[test]
+fn lines_and_columns() {
+    let code = source("let a;\n\nlet bc;\n");
+    assert_eq!(code.get_line_and_column(0), (1, 1));
+    assert_eq!(code.get_line_and_column(6), (1, 7));
+    assert_eq!(code.get_line_and_column(7), (2, 1));
+    assert_eq!(code.get_line_and_column(8), (3, 1));
+    assert_eq!(code.get_line_and_column(15), (3, 8));
+    assert_eq!(code.get_line_and_column(16), (4, 1));
+    // Past the end
+    assert_eq!(code.get_line_and_column(17), (4, 0));
+
+    assert_eq!(code.line_index(7), Some(1));
+    assert_eq!(code.line_index(17), None);
+    assert_eq!(code.line_span(2), (8, 15));
+    assert_eq!(code.line(2).iter().collect::<String>(), "let bc;");
+}
+
+#[test]
+fn text_ranges_include_both_ends() {
+    let code = source("let a;\nlet b;");
+    assert_eq!(code.get_text(4, 4).as_deref(), Some("a"));
+    assert_eq!(code.get_text(4, 11).as_deref(), Some("a;\nlet b"));
+    assert_eq!(code.get_text(7, 13).as_deref(), Some("let b;\n"));
+    assert_eq!(code.get_text(7, 14), None);
+    assert_eq!(code.get_text(5, 4), None);
+}
+
+#[test]
+fn byte_offsets_count_utf8() {
+    let code = source("é = 1;\n名 = \"😀\";");
+    assert_eq!(code.byte_offset(0), 0);
+    assert_eq!(code.byte_offset(1), 2);
+    // `名` on the second line
+    assert_eq!(code.byte_offset(7), 8);
+    assert_eq!(code.byte_offset(8), 11);
+    // After the emoji
+    assert_eq!(code.byte_offset(13), 19);
+    assert_eq!(code.byte_offset(code.total_chars), "é = 1;\n名 = \"😀\";\n".len());
+}
@@ -0,0 +1,70 @@
+use hylo_core::compiler::HyloCompiler;
+use hylo_core::lexer::source_code::SourceCodeContainer;
+use hylo_core::hylo_error::{suggestion, Applicability, Error, ErrorKind, Span, Suggestion};
+
+// Applies every machine applicable suggestion the compiler makes for `source`
+fn fix(source: &str) -> String {
+    let source_code = SourceCodeContainer::from(String::from(source));
+    let diagnostics = match HyloCompiler::new().compile(&source_code, "test.hy") {
+        Ok(compilation) => compilation.warnings,
+        Err(diagnostics) => diagnostics,
+    };
+    let suggestions: Vec<&Suggestion> = diagnostics.iter()
+        .flat_map(|e| &e.details.suggestions)
+        .filter(|suggestion| suggestion.applicability == Applicability::MachineApplicable)
+        .collect();
+    suggestion::apply_suggestions(source, &suggestions).0
+}
+
+#[test]
+fn machine_applicable_fixes() {
+    assert_eq!(fix("let a = 1\nlet b = 2;\n"), "let a = 1;\nlet b = 2;\n");
+    assert_eq!(fix("let a = f(1, g(2);\n"), "let a = f(1, g(2));\n");
+    assert_eq!(fix("let a = xs[0;\n"), "let a = xs[0];\n");
+    assert_eq!(fix("if (a) {}\nwhile(é) {}\n"), "if a {}\nwhile é {}\n");
+    assert_eq!(fix("while true { break; }\n"), "loop { break; }\n");
+}
+
+#[test]
+fn unsure_fixes_are_not_applied() {
+    // The `)` could belong after `b` as well as after `a`
+    let source = "f(a b);\n";
+    assert_eq!(fix(source), source);
+}
+
+#[test]
+fn overlapping_suggestions_apply_once() {
+    // `while (true)` gets a suggestion for the loop and one for the parentheses
+    assert_eq!(fix("while (true) { break; }\n"), "loop { break; }\n");
+
+    let first = Suggestion::new("a", 0..3, "x", Applicability::MachineApplicable);
+    let second = Suggestion::new("b", 2..4, "y", Applicability::MachineApplicable);
+    let third = Suggestion::new("c", 4..4, "z", Applicability::MachineApplicable);
+    let (fixed, applied) = suggestion::apply_suggestions("abcdef", &[&second, &third, &first]);
+    assert_eq!(fixed, "xdzef");
+    assert_eq!(applied, vec![&first, &third]);
+}
+
+#[test]
+fn suggestions_render_as_a_diff() {
+    let source_code = SourceCodeContainer::from(String::from("let a = f(1,\n    2;\n"));
+    let error = Error::new(ErrorKind::SyntaxError, Span { start: 18, stop: 18 }, Some("test.hy"))
+        .add_msg("Expected ')'")
+        .add_suggestion(Suggestion::new("Close the call", 18..18, ")", Applicability::MachineApplicable))
+        .add_suggestion(Suggestion::new("Put it on one line", 12..17, " ", Applicability::MaybeIncorrect));
+
+    assert_eq!(error.pretty(Some(&source_code)), "\
+❌ ERROR [E0001]: SyntaxError
+   --> test.hy:2:6
+    |
+  2 | 2;
+    |  ^ Expected ')'
+🔧 Suggestion: Close the call
+  2 -     2;
+  2 +     2);
+🔧 Suggestion: Put it on one line
+  1 - let a = f(1,
+  2 -     2;
+  1 + let a = f(1, 2;
+");
+}
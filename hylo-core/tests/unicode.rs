@@ -0,0 +1,192 @@
+use hylo_core::compiler::HyloCompiler;
+use hylo_core::lexer::source_code::SourceCodeContainer;
+use hylo_core::hylo_error::{output, Error, ErrorFormat, ErrorKind, Span, Theme};
+
+fn source(text: &str) -> SourceCodeContainer {
+    SourceCodeContainer::from(String::from(text))
+}
+
+// The source line and its underline of an error from char `start` to `stop`
+fn snippet(text: &str, start: usize, stop: usize) -> [String; 2] {
+    let error = Error::new(ErrorKind::SyntaxError, Span { start, stop }, Some("test.hy")).add_msg("here");
+    let report = error.pretty_with(Some(&source(text)), &Theme::ascii());
+    let lines: Vec<&str> = report.lines().collect();
+    return [String::from(lines[3]), String::from(lines[4])];
+}
+
+fn char_pos(text: &str, needle: &str) -> usize {
+    return text[..text.find(needle).unwrap()].chars().count();
+}
+
+#[test]
+fn tabs_are_expanded() {
+    let text = "\tlet x = @;";
+    assert_eq!(snippet(text, char_pos(text, "@"), char_pos(text, "@")), [
+        "  1 | let x = @;",
+        "    |         ^ here",
+    ]);
+
+    let text = "a\tb = @;";
+    assert_eq!(snippet(text, char_pos(text, "@"), char_pos(text, "@")), [
+        "  1 | a   b = @;",
+        "    |         ^ here",
+    ]);
+    assert_eq!(snippet(text, 1, 1), [
+        "  1 | a   b = @;",
+        "    |  ^^^ here",
+    ]);
+}
+
+#[test]
+fn tab_and_space_indentation_is_removed_by_width() {
+    let text = "\tlet a = f(1,\n    @;\n";
+    let error = Error::new(ErrorKind::SyntaxError, Span { start: 18, stop: 18 }, Some("test.hy"))
+        .add_msg("here")
+        .add_label(Span { start: 10, stop: 10 }, "opened");
+    assert_eq!(error.pretty_with(Some(&source(text)), &Theme::ascii()), "\
+ERROR [E0001]: SyntaxError
+   --> test.hy:2:5
+    |
+  1 | let a = f(1,
+    |          - opened
+  2 | @;
+    | ^ here
+");
+}
+
+#[test]
+fn wide_chars_take_two_columns() {
+    let text = "let 名前 = \"日本語\" + @;";
+    assert_eq!(snippet(text, char_pos(text, "@"), char_pos(text, "@")), [
+        "  1 | let 名前 = \"日本語\" + @;",
+        "    |                       ^ here",
+    ]);
+    assert_eq!(snippet(text, char_pos(text, "名"), char_pos(text, "前")), [
+        "  1 | let 名前 = \"日本語\" + @;",
+        "    |     ^^^^ here",
+    ]);
+    // Fullwidth forms and Hangul
+    let text = "ＡＢ 한글 @";
+    assert_eq!(snippet(text, char_pos(text, "@"), char_pos(text, "@")), [
+        "  1 | ＡＢ 한글 @",
+        "    |           ^ here",
+    ]);
+}
+
+#[test]
+fn combining_marks_belong_to_their_char() {
+    // `é` written as `e` and U+0301
+    let text = "let e\u{301}t\u{e9} = @;";
+    assert_eq!(snippet(text, char_pos(text, "@"), char_pos(text, "@")), [
+        "  1 | let e\u{301}t\u{e9} = @;",
+        "    |           ^ here",
+    ]);
+    // Pointing at the mark points at the char it sits on
+    assert_eq!(snippet(text, 5, 5), [
+        "  1 | let e\u{301}t\u{e9} = @;",
+        "    |     ^ here",
+    ]);
+    // Pointing at the base covers the mark as well
+    assert_eq!(snippet(text, 4, 6), [
+        "  1 | let e\u{301}t\u{e9} = @;",
+        "    |     ^^ here",
+    ]);
+}
+
+#[test]
+fn hangul_jamo_sequences_are_one_syllable() {
+    // `한글` written as leading consonant, vowel and final consonant jamo
+    let text = "\u{1112}\u{1161}\u{11AB}\u{1100}\u{1173}\u{11AF} @";
+    assert_eq!(snippet(text, char_pos(text, "@"), char_pos(text, "@")), [
+        format!("  1 | {}", text),
+        String::from("    |      ^ here"),
+    ]);
+    assert_eq!(snippet(text, 3, 5), [
+        format!("  1 | {}", text),
+        String::from("    |   ^^ here"),
+    ]);
+}
+
+#[test]
+fn indic_marks_belong_to_their_consonant() {
+    // The conjunct `क्ष` with the spacing vowel sign `ि` after it
+    let text = "let \u{915}\u{94D}\u{937}\u{93F} = @;";
+    assert_eq!(snippet(text, char_pos(text, "@"), char_pos(text, "@")), [
+        format!("  1 | {}", text),
+        String::from("    |           ^ here"),
+    ]);
+    // The virama sits on the first consonant, the vowel sign takes a column of its own
+    assert_eq!(snippet(text, 5, 5), [
+        format!("  1 | {}", text),
+        String::from("    |     ^ here"),
+    ]);
+    assert_eq!(snippet(text, 6, 6), [
+        format!("  1 | {}", text),
+        String::from("    |      ^^ here"),
+    ]);
+
+    // Tamil `தமிழ்`, the final virama takes no column
+    let text = "\u{BA4}\u{BAE}\u{BBF}\u{BB4}\u{BCD} = @;";
+    assert_eq!(snippet(text, char_pos(text, "@"), char_pos(text, "@")), [
+        format!("  1 | {}", text),
+        String::from("    |        ^ here"),
+    ]);
+}
+
+#[test]
+fn emoji_sequences_are_one_cluster() {
+    // Family (ZWJ sequence), flag (regional indicators), thumbs up with a skin tone
+    for emoji in ["\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}", "\u{1F1EB}\u{1F1F7}", "\u{1F44D}\u{1F3FD}"] {
+        let text = format!("\"{}\" @", emoji);
+        assert_eq!(snippet(&text, char_pos(&text, "@"), char_pos(&text, "@")), [
+            format!("  1 | \"{}\" @", emoji),
+            String::from("    |      ^ here"),
+        ], "{:?}", emoji);
+        assert_eq!(snippet(&text, 1, emoji.chars().count()), [
+            format!("  1 | \"{}\" @", emoji),
+            String::from("    |  ^^ here"),
+        ], "{:?}", emoji);
+    }
+
+    // A text style char followed by the emoji presentation selector
+    let text = "\"\u{26A0}\u{FE0F}\" @";
+    assert_eq!(snippet(text, char_pos(text, "@"), char_pos(text, "@")), [
+        "  1 | \"\u{26A0}\u{FE0F}\" @",
+        "    |      ^ here",
+    ]);
+}
+
+#[test]
+fn multi_line_labels_over_wide_chars() {
+    let text = "let s = \"日本\n語\";";
+    let error = Error::new(ErrorKind::SyntaxError, Span { start: 8, stop: 13 }, Some("test.hy")).add_msg("here");
+    assert_eq!(error.pretty_with(Some(&source(text)), &Theme::ascii()), "\
+ERROR [E0001]: SyntaxError
+   --> test.hy:1:9
+    |
+  1 | let s = \"日本
+    |         ^^^^^
+  2 | 語\";
+    | ^^^ here
+");
+}
+
+#[test]
+fn non_ascii_programs_render_in_every_format() {
+    let sources = [
+        "let 名前 = @;\n",
+        "let s = \"日本語\\q\";\n",
+        "\tlet é = \"\u{1F600}\" #\n",
+        "let x = \"未完",
+        "/* コメント",
+        "let a = 1;\nlet 数 = (1 + 2;\n",
+    ];
+    for text in sources {
+        let source_code = source(text);
+        let diagnostics = HyloCompiler::new().compile(&source_code, "test.hy").unwrap_err();
+        for format in [ErrorFormat::Human, ErrorFormat::Short, ErrorFormat::Json, ErrorFormat::Sarif] {
+            let rendered = output::render(format, &[(&diagnostics, &source_code)], None, &Theme::default());
+            assert!(!rendered.is_empty(), "{:?} for {:?}", format, text);
+        }
+    }
+}
@@ -0,0 +1,315 @@
+/*
+ * COMMAND LINE
+ *
+ * hylo <COMMAND> [OPTIONS] FILE...
+ * hylo --emit=KIND FILE
+ *
+ * A FILE of `-` reads the program from stdin.
+ */
+use hylo_core::emit::EmitKind;
+use hylo_core::hylo_error::{ColorChoice, ErrorFormat, ErrorKind, Glyphs};
+use hylo_core::lint::{LintLevel, LintLevels};
+
+pub const USAGE: &str = "\
+Usage: hylo <COMMAND> [OPTIONS] FILE
+       hylo --emit=KIND FILE
+
+Commands:
+  run      Compile and run a program
+  check    Report errors without producing any output
+  build    Compile a program
+  lex      Print the tokens of a program, same as --emit=tokens
+  parse    Print the syntax tree of a program, same as --emit=ast
+  fmt      Format files in place
+  fix      Apply the suggested fixes to files in place
+  explain  Explain an error or warning code, e.g. `hylo explain E0002`
+
+Options:
+  --emit=KIND        Print a stage of the compiler: tokens, ast, ast-json, ir or bytecode
+  --error-limit N    Print at most N errors, the rest are only counted
+  --error-format=F   Print errors as human, short, json (one object per line) or sarif
+  --color=WHEN       Colour errors: auto (when stderr is a terminal), always or never
+  --theme=T          Mark errors with emoji (the default) or plain ascii
+  -A, -W, -D CODE    Allow, warn about or deny a warning, by code (W0001) or name
+  --deny-warnings    Turn the warnings not given a level into errors
+  -h, --help         Print this help
+  -V, --version      Print the version
+
+Use `-` as FILE to read from stdin.
+Run `hylo fmt --help` or `hylo fix --help` for their options.";
+
+pub const FMT_USAGE: &str = "\
+Usage: hylo fmt [--check] [--width N] FILE...
+
+Options:
+  --check      Only check, exit with 1 when a file is not formatted
+  --width N    Break lines longer than N columns (default 100)
+
+A FILE of `-` formats stdin and prints the result.";
+
+pub const FIX_USAGE: &str = "\
+Usage: hylo fix [--dry-run] FILE...
+
+Applies the suggestions that are sure to be right, such as a missing ';'.
+
+Options:
+  --dry-run    Print the changes instead of writing them
+
+A FILE of `-` fixes stdin and prints the result.";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Stdin,
+    File(String),
+}
+
+impl Input {
+    fn from(arg: &str) -> Self {
+        if arg == "-" { Input::Stdin } else { Input::File(String::from(arg)) }
+    }
+
+    // The name used in diagnostics
+    pub fn name(&self) -> &str {
+        match self {
+            Input::Stdin => "<stdin>",
+            Input::File(path) => path,
+        }
+    }
+
+    pub fn read(&self) -> Result<String, String> {
+        let result = match self {
+            Input::Stdin => std::io::read_to_string(std::io::stdin()),
+            Input::File(path) => std::fs::read_to_string(path),
+        };
+        return result.map_err(|e| format!("could not read `{}`: {}", self.name(), describe_io_error(&e)));
+    }
+}
+
+// The io error without the "(os error N)" suffix
+pub fn describe_io_error(error: &std::io::Error) -> String {
+    match error.kind() {
+        std::io::ErrorKind::NotFound => String::from("no such file"),
+        std::io::ErrorKind::PermissionDenied => String::from("permission denied"),
+        std::io::ErrorKind::IsADirectory => String::from("it is a directory"),
+        std::io::ErrorKind::InvalidData => String::from("it is not valid UTF-8"),
+        _ => error.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Run(Input),
+    Check(Input),
+    Build(Input),
+    Emit { kind: EmitKind, input: Input },
+    Fmt { check: bool, width: Option<usize>, inputs: Vec<Input> },
+    Fix { dry_run: bool, inputs: Vec<Input> },
+    Explain(ErrorKind),
+    Help(&'static str),
+    Version,
+}
+
+// Options that apply to every command
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    pub error_limit: Option<usize>,
+    pub error_format: ErrorFormat,
+    pub color: ColorChoice,
+    pub glyphs: Glyphs,
+    pub lint_levels: LintLevels,
+}
+
+pub fn parse_args(args: &[String]) -> Result<(Command, Options), String> {
+    let (options, args) = parse_options(args)?;
+    return Ok((parse_command(&args)?, options));
+}
+
+// Takes the global options out of `args`, wherever they are
+fn parse_options(args: &[String]) -> Result<(Options, Vec<String>), String> {
+    let mut options = Options::default();
+    let mut rest = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+            _ => (arg.as_str(), None)
+        };
+
+        match name {
+            "--error-limit" => {
+                let value = inline_value.or_else(|| args.next().cloned());
+                match value.and_then(|value| value.parse().ok()) {
+                    Some(limit) => options.error_limit = Some(limit),
+                    None => return Err(String::from("`--error-limit` expects a number"))
+                }
+            },
+            "--error-format" => {
+                let value = inline_value.or_else(|| args.next().cloned());
+                match value.as_deref().and_then(ErrorFormat::from) {
+                    Some(format) => options.error_format = format,
+                    None => return Err(String::from("`--error-format` expects human, short, json or sarif"))
+                }
+            },
+            "--color" => {
+                let value = inline_value.or_else(|| args.next().cloned());
+                match value.as_deref().and_then(ColorChoice::from) {
+                    Some(color) => options.color = color,
+                    None => return Err(String::from("`--color` expects auto, always or never"))
+                }
+            },
+            "--theme" => {
+                let value = inline_value.or_else(|| args.next().cloned());
+                match value.as_deref().and_then(Glyphs::from) {
+                    Some(glyphs) => options.glyphs = glyphs,
+                    None => return Err(String::from("`--theme` expects emoji or ascii"))
+                }
+            },
+            "--deny-warnings" => options.lint_levels.set_deny_warnings(true),
+            "-A" | "-W" | "-D" => {
+                let Some(warning) = args.next() else {
+                    return Err(format!("`{}` expects a warning code or name", name));
+                };
+                options.lint_levels.set(warning_kind(warning)?, lint_level(name));
+            },
+            // `-AW0001`
+            _ if ["-A", "-W", "-D"].iter().any(|flag| name.starts_with(flag)) => {
+                options.lint_levels.set(warning_kind(&name[2..])?, lint_level(&name[..2]));
+            },
+            _ => rest.push(arg.clone()),
+        }
+    }
+
+    return Ok((options, rest));
+}
+
+fn lint_level(flag: &str) -> LintLevel {
+    match flag {
+        "-A" => LintLevel::Allow,
+        "-D" => LintLevel::Deny,
+        _ => LintLevel::Warn,
+    }
+}
+
+fn warning_kind(name: &str) -> Result<ErrorKind, String> {
+    ErrorKind::warning_from(name).ok_or_else(|| {
+        let known: Vec<String> = ErrorKind::WARNINGS.iter()
+            .map(|kind| format!("{} ({})", kind.code(), kind.lint_name().unwrap()))
+            .collect();
+        format!("unknown warning `{}`, expected one of {}", name, known.join(", "))
+    })
+}
+
+fn error_kind(code: &str) -> Result<ErrorKind, String> {
+    ErrorKind::from_code(code).ok_or_else(|| {
+        let known: Vec<&str> = ErrorKind::ALL.iter().map(|kind| kind.code()).collect();
+        format!("unknown code `{}`, expected one of {}", code, known.join(", "))
+    })
+}
+
+fn parse_command(args: &[String]) -> Result<Command, String> {
+    let (command, rest) = match args.split_first() {
+        Some(split) => split,
+        None => return Ok(Command::Help(USAGE))
+    };
+
+    let command = match command.as_str() {
+        "-h" | "--help" | "help" => Command::Help(USAGE),
+        "-V" | "--version" => Command::Version,
+        "run" => Command::Run(single_input(command, rest)?),
+        "check" => Command::Check(single_input(command, rest)?),
+        "build" => Command::Build(single_input(command, rest)?),
+        "lex" => Command::Emit { kind: EmitKind::Tokens, input: single_input(command, rest)? },
+        "parse" => Command::Emit { kind: EmitKind::Ast, input: single_input(command, rest)? },
+        "--emit" => match rest.split_first() {
+            Some((kind, rest)) => Command::Emit { kind: emit_kind(kind)?, input: single_input("--emit", rest)? },
+            None => return Err(String::from("`--emit` expects a kind"))
+        },
+        _ if command.starts_with("--emit=") => Command::Emit {
+            kind: emit_kind(&command["--emit=".len()..])?,
+            input: single_input("--emit", rest)?
+        },
+        "fmt" => parse_fmt_args(rest)?,
+        "fix" => parse_fix_args(rest)?,
+        "explain" => match rest {
+            [code] => Command::Explain(error_kind(code)?),
+            _ => return Err(String::from("`explain` expects a code, such as E0001"))
+        },
+        _ => return Err(format!("unknown command `{}`", command))
+    };
+
+    return Ok(command);
+}
+
+// Stages that aren't produced yet are rejected before any file is read
+fn emit_kind(name: &str) -> Result<EmitKind, String> {
+    match EmitKind::from(name) {
+        Some(kind) if kind.is_available() => Ok(kind),
+        Some(kind) => Err(format!("`--emit={}` is not available yet, the compiler stops after parsing", kind.as_str())),
+        None => Err(format!("unknown `--emit` kind `{}`, expected tokens, ast, ast-json, ir or bytecode", name)),
+    }
+}
+
+fn single_input(command: &str, args: &[String]) -> Result<Input, String> {
+    let mut inputs = Vec::new();
+    for arg in args {
+        if arg.starts_with('-') && arg != "-" {
+            return Err(format!("unknown option `{}` for `{}`", arg, command));
+        }
+        inputs.push(Input::from(arg));
+    }
+
+    match inputs.len() {
+        1 => Ok(inputs.remove(0)),
+        0 => Err(format!("`{}` expects a file, use `-` for stdin", command)),
+        _ => Err(format!("`{}` expects a single file", command)),
+    }
+}
+
+fn parse_fmt_args(args: &[String]) -> Result<Command, String> {
+    let mut check = false;
+    let mut width = None;
+    let mut inputs = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help(FMT_USAGE)),
+            "--check" => check = true,
+            "--width" => match args.next().and_then(|w| w.parse().ok()) {
+                Some(w) => width = Some(w),
+                None => return Err(String::from("`--width` expects a number"))
+            },
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("unknown option `{}` for `fmt`", arg));
+            },
+            _ => inputs.push(Input::from(arg)),
+        }
+    }
+
+    if inputs.is_empty() {
+        return Err(String::from("`fmt` expects at least one file, use `-` for stdin"));
+    }
+    return Ok(Command::Fmt { check, width, inputs });
+}
+
+fn parse_fix_args(args: &[String]) -> Result<Command, String> {
+    let mut dry_run = false;
+    let mut inputs = Vec::new();
+
+    for arg in args {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help(FIX_USAGE)),
+            "--dry-run" => dry_run = true,
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("unknown option `{}` for `fix`", arg));
+            },
+            _ => inputs.push(Input::from(arg)),
+        }
+    }
+
+    if inputs.is_empty() {
+        return Err(String::from("`fix` expects at least one file, use `-` for stdin"));
+    }
+    return Ok(Command::Fix { dry_run, inputs });
+}
@@ -1,9 +1,231 @@
+mod cli;
+
+use cli::{Command, Input, Options};
 use hylo_core::compiler::HyloCompiler;
+use hylo_core::emit::{self, EmitKind};
+use hylo_core::hylo_error::{self, output, suggestion, Applicability, Diagnostics, Suggestion, Theme};
+use hylo_core::formatter::{format_source, FormatOptions};
+use hylo_core::lexer::source_code::SourceCodeContainer;
+use std::io::IsTerminal;
 
 fn main() {
-    let compiler = HyloCompiler::new();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (command, options) = match cli::parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(msg) => {
+            eprintln!("error: {}\n\n{}", msg, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    std::process::exit(run(command, &options));
+}
+
+fn run(command: Command, options: &Options) -> i32 {
+    let compiler = HyloCompiler::new().with_lint_levels(options.lint_levels.clone());
+
+    match command {
+        Command::Help(usage) => {
+            println!("{}", usage);
+            return 0;
+        },
+        Command::Version => {
+            println!("hylo {}", env!("CARGO_PKG_VERSION"));
+            return 0;
+        },
+        Command::Check(input) => {
+            let Some(source_code) = read_source(&input) else { return 1 };
+            match compiler.compile(&source_code, input.name()) {
+                Ok(compilation) => return report(&compilation.warnings, &source_code, options),
+                Err(diagnostics) => return report(&diagnostics, &source_code, options),
+            }
+        },
+        Command::Run(input) | Command::Build(input) => {
+            let Some(source_code) = read_source(&input) else { return 1 };
+            match compiler.compile(&source_code, input.name()) {
+                Ok(compilation) => report(&compilation.warnings, &source_code, options),
+                Err(diagnostics) => return report(&diagnostics, &source_code, options),
+            };
+            eprintln!("error: `{}` compiled without errors, but there is no VM to run it yet", input.name());
+            return 1;
+        },
+        Command::Emit { kind, input } => {
+            let Some(source_code) = read_source(&input) else { return 1 };
+            if kind == EmitKind::Tokens {
+                match compiler.tokenize(&source_code, input.name()) {
+                    Ok(token_containers) => print!("{}", emit::dump_tokens(&token_containers, &source_code)),
+                    Err(diagnostics) => return report(&diagnostics, &source_code, options),
+                }
+                return 0;
+            }
+
+            let compilation = match compiler.compile(&source_code, input.name()) {
+                Ok(compilation) => compilation,
+                Err(diagnostics) => return report(&diagnostics, &source_code, options),
+            };
+            report(&compilation.warnings, &source_code, options);
+            match kind {
+                EmitKind::Ast => print!("{}", emit::dump_ast(&compilation.program, &source_code)),
+                EmitKind::AstJson => println!("{}", emit::ast_to_json(&compilation.program).to_pretty()),
+                // `--emit=tokens` is handled above, the rest is rejected by the command line
+                EmitKind::Tokens | EmitKind::Ir | EmitKind::Bytecode => unreachable!(),
+            }
+            return 0;
+        },
+        Command::Fmt { check, width, inputs } => {
+            let mut format_options = FormatOptions::default();
+            if let Some(width) = width {
+                format_options.max_width = width;
+            }
+            return fmt(&inputs, check, &format_options, options);
+        },
+        Command::Fix { dry_run, inputs } => return fix(&compiler, &inputs, dry_run, options),
+        Command::Explain(kind) => {
+            print!("{}", hylo_error::index::explanation(&kind));
+            return 0;
+        },
+    }
+}
+
+// Prints the errors and warnings in the chosen format, returns the exit code for them
+fn report(diagnostics: &Diagnostics, source_code: &SourceCodeContainer, options: &Options) -> i32 {
+    return report_all(&[(diagnostics, source_code)], options);
+}
+
+fn report_all(files: &[(&Diagnostics, &SourceCodeContainer)], options: &Options) -> i32 {
+    let theme = Theme::new(options.glyphs, options.color.enabled(std::io::stderr().is_terminal()));
+    eprint!("{}", output::render(options.error_format, files, options.error_limit, &theme));
+    return files.iter().map(|(diagnostics, _)| diagnostics.exit_code()).find(|&code| code != 0).unwrap_or(0);
+}
+
+fn read_source(input: &Input) -> Option<SourceCodeContainer> {
+    match input.read() {
+        Ok(source) => Some(SourceCodeContainer::from(source)),
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            None
+        }
+    }
+}
+
+/*
+ * Rewrites the files in place, stdin is printed to stdout. With `check`
+ * nothing is written and the exit code is 1 when any input is not formatted.
+ * Files that don't parse are reported together at the end.
+ */
+fn fmt(inputs: &[Input], check: bool, format_options: &FormatOptions, options: &Options) -> i32 {
+    let mut exit_code = 0;
+    let mut failed: Vec<(Diagnostics, SourceCodeContainer)> = Vec::new();
+    for input in inputs {
+        let source = match input.read() {
+            Ok(source) => source,
+            Err(msg) => {
+                eprintln!("error: {}", msg);
+                exit_code = 1;
+                continue;
+            }
+        };
+
+        let source_code = SourceCodeContainer::from(source.clone());
+        let formatted = match format_source(&source_code, input.name(), format_options) {
+            Ok(formatted) => formatted,
+            Err(errors) => {
+                failed.push((Diagnostics::from(errors), source_code));
+                continue;
+            }
+        };
+
+        if check {
+            if formatted != source {
+                println!("{} is not formatted", input.name());
+                exit_code = 1;
+            }
+            continue;
+        }
+
+        match input {
+            Input::Stdin => print!("{}", formatted),
+            Input::File(path) => {
+                if formatted == source { continue; }
+                if let Err(e) = std::fs::write(path, formatted) {
+                    eprintln!("error: could not write `{}`: {}", path, cli::describe_io_error(&e));
+                    exit_code = 1;
+                }
+            }
+        }
+    }
+
+    let files: Vec<(&Diagnostics, &SourceCodeContainer)> = failed.iter()
+        .map(|(diagnostics, source_code)| (diagnostics, source_code))
+        .collect();
+    let report_code = report_all(&files, options);
+    return if report_code != 0 { report_code } else { exit_code };
+}
+
+/*
+ * Applies the machine applicable suggestions and rewrites the files, stdin
+ * is printed to stdout. With `dry_run` the changes are printed instead.
+ * What no suggestion fixes is reported at the end against the fixed source,
+ * and fails the command when errors remain.
+ */
+fn fix(compiler: &HyloCompiler, inputs: &[Input], dry_run: bool, options: &Options) -> i32 {
+    let mut exit_code = 0;
+    let mut remaining: Vec<(Diagnostics, SourceCodeContainer)> = Vec::new();
+    for input in inputs {
+        let source = match input.read() {
+            Ok(source) => source,
+            Err(msg) => {
+                eprintln!("error: {}", msg);
+                exit_code = 1;
+                continue;
+            }
+        };
+
+        let source_code = SourceCodeContainer::from(source.clone());
+        let diagnostics = match compiler.compile(&source_code, input.name()) {
+            Ok(compilation) => compilation.warnings,
+            Err(diagnostics) => diagnostics,
+        };
+        let suggestions: Vec<&Suggestion> = diagnostics.iter()
+            .flat_map(|e| &e.details.suggestions)
+            .filter(|suggestion| suggestion.applicability == Applicability::MachineApplicable)
+            .collect();
+        let (fixed, applied) = suggestion::apply_suggestions(&source, &suggestions);
+
+        let fixed_code = SourceCodeContainer::from(fixed.clone());
+        let left = match compiler.compile(&fixed_code, input.name()) {
+            Ok(compilation) => compilation.warnings,
+            Err(diagnostics) => diagnostics,
+        };
+        if !left.is_empty() {
+            remaining.push((left, fixed_code));
+        }
+
+        if dry_run {
+            for suggestion in &applied {
+                println!("{}: {}", input.name(), suggestion.message);
+                print!("{}", suggestion.diff(&source_code).unwrap_or_default());
+            }
+            continue;
+        }
+
+        match input {
+            Input::Stdin => print!("{}", fixed),
+            Input::File(path) => {
+                if applied.is_empty() { continue; }
+                if let Err(e) = std::fs::write(path, fixed) {
+                    eprintln!("error: could not write `{}`: {}", path, cli::describe_io_error(&e));
+                    exit_code = 1;
+                    continue;
+                }
+                eprintln!("Fixed {} problem{} in {}", applied.len(), if applied.len() == 1 { "" } else { "s" }, path);
+            }
+        }
+    }
 
-    // TODO: Change
-    let file_data = std::fs::read_to_string("test.hy").unwrap();
-    compiler.compile_stdin(file_data);
+    let files: Vec<(&Diagnostics, &SourceCodeContainer)> = remaining.iter()
+        .map(|(diagnostics, source_code)| (diagnostics, source_code))
+        .collect();
+    let report_code = report_all(&files, options);
+    return if report_code != 0 { report_code } else { exit_code };
 }
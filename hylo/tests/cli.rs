@@ -0,0 +1,253 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn hylo(args: &[&str], stdin: &str) -> Output {
+    hylo_with_env(args, stdin, &[])
+}
+
+fn hylo_with_env(args: &[&str], stdin: &str, env: &[(&str, &str)]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_hylo"))
+        .args(args)
+        .env_remove("NO_COLOR")
+        .envs(env.iter().cloned())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // A usage error exits before reading stdin, so the pipe may already be closed
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+fn temp_file(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!("hylo-cli-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn missing_file_is_reported() {
+    let output = hylo(&["check", "does/not/exist.hy"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "error: could not read `does/not/exist.hy`: no such file\n");
+}
+
+#[test]
+fn diagnostics_use_the_file_name() {
+    let path = temp_file("bad.hy", "let x = @;\n");
+    let output = hylo(&["check", &path], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains(&format!("--> {}:1:9", path)));
+
+    let output = hylo(&["check", "-"], "let x = @;\n");
+    assert!(stderr(&output).contains("--> <stdin>:1:9"));
+}
+
+#[test]
+fn check_accepts_valid_programs() {
+    let output = hylo(&["check", "-"], "let x = 1;\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty() && output.stderr.is_empty());
+}
+
+#[test]
+fn bad_usage_exits_with_2() {
+    assert_eq!(hylo(&["frobnicate"], "").status.code(), Some(2));
+    assert_eq!(hylo(&["check"], "").status.code(), Some(2));
+    assert_eq!(hylo(&["check", "a.hy", "b.hy"], "").status.code(), Some(2));
+    assert_eq!(hylo(&["parse", "--verbose", "a.hy"], "").status.code(), Some(2));
+}
+
+#[test]
+fn fmt_check_reports_unformatted_files() {
+    let formatted = temp_file("formatted.hy", "let x = 1;\n");
+    let messy = temp_file("messy.hy", "let  x=1 ;");
+
+    assert_eq!(hylo(&["fmt", "--check", &formatted], "").status.code(), Some(0));
+    let output = hylo(&["fmt", "--check", &formatted, &messy], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), format!("{} is not formatted\n", messy));
+
+    assert_eq!(hylo(&["fmt", &messy], "").status.code(), Some(0));
+    assert_eq!(std::fs::read_to_string(&messy).unwrap(), "let x = 1;\n");
+}
+
+#[test]
+fn fmt_formats_stdin_to_stdout() {
+    let output = hylo(&["fmt", "-"], "x=  1;");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "x = 1;\n");
+}
+
+#[test]
+fn emit_prints_pipeline_stages() {
+    let output = hylo(&["--emit=tokens", "-"], "x;");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1:1     word      x\n1:2     punct     ;\n");
+
+    let output = hylo(&["--emit", "ast", "-"], "x;");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "Program\n  ExprStmt @1:1\n    Word x @1:1\n");
+
+    let output = hylo(&["--emit=ast-json", "-"], "x;");
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("{\n  \"kind\": \"Program\""));
+
+    // Rejected before the file is read
+    let output = hylo(&["--emit=ir", "does/not/exist.hy"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("`--emit=ir` is not available yet"));
+    assert!(!stderr(&output).contains("could not read"));
+
+    assert_eq!(hylo(&["--emit=machine-code", "-"], "x;").status.code(), Some(2));
+}
+
+#[test]
+fn every_error_is_reported_with_a_summary() {
+    let source = "let a = @;\nlet b = #;\nlet c = $;\n";
+    let output = hylo(&["check", "-"], source);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output).matches("ERROR [E0001]").count(), 3);
+    assert!(stderr(&output).ends_with("❌ Found 3 errors\n"));
+
+    let output = hylo(&["check", "--error-limit", "1", "-"], source);
+    assert_eq!(stderr(&output).matches("ERROR [E0001]").count(), 1);
+    assert!(stderr(&output).ends_with("❌ Found 3 errors, 2 more not shown\n"));
+
+    assert_eq!(hylo(&["check", "--error-limit=many", "-"], source).status.code(), Some(2));
+}
+
+#[test]
+fn lexing_and_parsing_errors_are_reported_together() {
+    let source = "let x = @;\nlet y = 1 +;\nfn f( {\n}\nlet z = 300u8;\n";
+    let output = hylo(&["check", "--error-format=short", "-"], source);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "\
+<stdin>:1:9: error[E0001]: The token is invalid
+<stdin>:2:12: error[E0001]: Expected an expression, found `;`
+<stdin>:3:7: error[E0001]: Expected a parameter name
+<stdin>:5:9: error[E0008]: Literal out of range for `u8`
+❌ Found 4 errors
+");
+}
+
+#[test]
+fn warning_levels_from_the_command_line() {
+    let source = "while true {\n    break;\n}\n";
+    let output = hylo(&["check", "-"], source);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stderr(&output).contains("WARNING [W0002]: WhileTrue"));
+    assert!(stderr(&output).ends_with("⚠️ Found 1 warning\n"));
+
+    let output = hylo(&["check", "-A", "while_true", "-"], source);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stderr.is_empty());
+
+    let output = hylo(&["check", "-DW0002", "-"], source);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("ERROR [W0002]: WhileTrue"));
+
+    assert_eq!(hylo(&["check", "--deny-warnings", "-"], source).status.code(), Some(1));
+    assert_eq!(hylo(&["check", "--deny-warnings", "-W", "W0002", "-"], source).status.code(), Some(0));
+    assert_eq!(hylo(&["check", "-A", "E0001", "-"], source).status.code(), Some(2));
+}
+
+#[test]
+fn error_format_selects_the_output() {
+    let source = "let x = @;\n";
+    let output = hylo(&["check", "--error-format=json", "-"], source);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with(r#"{"code":"E0001","kind":"SyntaxError","severity":"error""#));
+    assert_eq!(stderr(&output).lines().count(), 1);
+
+    let output = hylo(&["check", "--error-format", "short", "-"], source);
+    assert!(stderr(&output).starts_with("<stdin>:1:9: error[E0001]: "));
+
+    let output = hylo(&["check", "--error-format=sarif", "-"], "let x = 1;\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stderr(&output).contains(r#""version": "2.1.0""#));
+
+    assert_eq!(hylo(&["check", "--error-format=xml", "-"], source).status.code(), Some(2));
+}
+
+#[test]
+fn fix_applies_suggestions() {
+    let source = "let a = 1\nwhile true { break; }\n";
+    let path = temp_file("fix.hy", source);
+
+    let output = hylo(&["fix", "--dry-run", &path], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), format!("\
+{0}: Add a ';' after the statement
+  1 - let a = 1
+  1 + let a = 1;
+{0}: Use `loop` for a loop that only ends with `break`
+  2 - while true {{ break; }}
+  2 + loop {{ break; }}
+", path));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), source);
+
+    let output = hylo(&["fix", &path], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stderr(&output), format!("Fixed 2 problems in {}\n", path));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "let a = 1;\nloop { break; }\n");
+
+    let output = hylo(&["fix", "-"], "let b = 2\n");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "let b = 2;\n");
+}
+
+#[test]
+fn fix_reports_what_it_cannot_fix() {
+    let output = hylo(&["fix", "--dry-run", "--error-format=short", "-"], "let a = 1\nlet b = @;\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("<stdin>: Add a ';' after the statement\n"));
+    assert_eq!(stderr(&output), "<stdin>:2:9: error[E0001]: The token is invalid\n❌ Found 1 error\n");
+
+    let path = temp_file("fix_remaining.hy", "let a = 1\nlet b = 1 +;\n");
+    let output = hylo(&["fix", "--error-format=short", &path], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), format!("\
+Fixed 1 problem in {0}
+{0}:2:12: error[E0001]: Expected an expression, found `;`
+❌ Found 1 error
+", path));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "let a = 1;\nlet b = 1 +;\n");
+}
+
+#[test]
+fn explain_prints_the_error_index() {
+    let output = hylo(&["explain", "e0002"], "");
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("# E0002: StringNotTerminated\n"), "{}", stdout);
+    assert!(stdout.contains("## Wrong") && stdout.contains("## Correct"));
+
+    let output = hylo(&["explain", "E9999"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("unknown code `E9999`, expected one of E0001, E0002"));
+}
+
+#[test]
+fn color_and_theme_options() {
+    let source = "let x = @;\n";
+
+    // Pipes aren't terminals, so auto leaves the output plain
+    let output = hylo(&["check", "-"], source);
+    assert!(!stderr(&output).contains('\x1b'));
+
+    let output = hylo(&["check", "--color=always", "-"], source);
+    assert!(stderr(&output).contains("\x1b[1;31mERROR [E0001]\x1b[0m"));
+    let output = hylo_with_env(&["check", "--color", "always", "-"], source, &[("NO_COLOR", "1")]);
+    assert!(stderr(&output).contains('\x1b'), "--color=always wins over NO_COLOR");
+    let output = hylo(&["check", "--color=never", "-"], source);
+    assert!(!stderr(&output).contains('\x1b'));
+
+    let output = hylo(&["check", "--theme=ascii", "-"], source);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("ERROR [E0001]: SyntaxError\n"));
+    assert!(stderr(&output).is_ascii());
+
+    assert_eq!(hylo(&["check", "--color=sometimes", "-"], source).status.code(), Some(2));
+    assert_eq!(hylo(&["check", "--theme=fancy", "-"], source).status.code(), Some(2));
+}
@@ -0,0 +1,147 @@
+#!/usr/bin/env python3
+"""
+UNICODE TABLES
+
+Generates hylo-core/src/hylo_error/unicode_tables.rs, the tables the
+diagnostic renderer measures source lines with, from the Unicode Character
+Database:
+
+    EastAsianWidth.txt                     wide and fullwidth chars
+    extracted/DerivedGeneralCategory.txt   marks and format chars
+    auxiliary/GraphemeBreakProperty.txt    grapheme cluster boundaries
+    emoji/emoji-data.txt                   Extended_Pictographic
+
+Run it from the root of the repository with the directory the files were
+downloaded to, keeping the layout of https://www.unicode.org/Public/UCD/latest/ucd/:
+
+    python3 scripts/unicode_tables.py path/to/ucd
+"""
+import os
+import re
+import sys
+
+OUTPUT = os.path.join("hylo-core", "src", "hylo_error", "unicode_tables.rs")
+
+# Grapheme_Cluster_Break values and the variants they become
+GRAPHEME_BREAKS = {
+    "CR": "CarriageReturn",
+    "LF": "LineFeed",
+    "Control": "Control",
+    "Extend": "Extend",
+    "ZWJ": "ZeroWidthJoiner",
+    "Regional_Indicator": "RegionalIndicator",
+    "Prepend": "Prepend",
+    "SpacingMark": "SpacingMark",
+    "L": "HangulL",
+    "V": "HangulV",
+    "T": "HangulT",
+    "LV": "HangulLV",
+    "LVT": "HangulLVT",
+}
+
+LINE = re.compile(r"^([0-9A-F]+)(?:\.\.([0-9A-F]+))?\s*;\s*([A-Za-z_]+)")
+VERSION = re.compile(r"^#\s*[A-Za-z-]+-(\d+\.\d+\.\d+)\.txt")
+
+
+# The (start, end, value) ranges of a UCD file and the Unicode version it is from
+def read(ucd, name):
+    ranges = []
+    version = None
+    with open(os.path.join(ucd, name), encoding="utf-8") as f:
+        for line in f:
+            if version is None:
+                found = VERSION.match(line)
+                if found:
+                    version = found.group(1)
+            found = LINE.match(line)
+            if not found:
+                continue
+            start = int(found.group(1), 16)
+            end = int(found.group(2), 16) if found.group(2) else start
+            ranges.append((start, end, found.group(3)))
+    if version is None:
+        sys.exit("error: no Unicode version in the header of {}".format(name))
+    return ranges, version
+
+
+# Sorted ranges with the adjacent ones of the same value joined
+def merge(ranges):
+    merged = []
+    for start, end, value in sorted(ranges):
+        if merged and merged[-1][2] == value and merged[-1][1] + 1 == start:
+            merged[-1] = (merged[-1][0], end, value)
+        else:
+            merged.append((start, end, value))
+    return merged
+
+
+def chars(ranges, values):
+    return {c for start, end, value in ranges if value in values for c in range(start, end + 1)}
+
+
+def to_ranges(char_set):
+    return [(start, end) for start, end, _ in merge((c, c, None) for c in char_set)]
+
+
+def rust_table(name, comment, row_type, cells, per_line):
+    lines = ["// {}".format(comment), "pub const {}: &[{}] = &[".format(name, row_type)]
+    for idx in range(0, len(cells), per_line):
+        lines.append("    " + " ".join(cells[idx:idx + per_line]))
+    lines.append("];")
+    return "\n".join(lines)
+
+
+def ranges_table(name, comment, ranges):
+    cells = ["(0x{:04X}, 0x{:04X}),".format(start, end) for start, end in ranges]
+    return rust_table(name, comment, "(u32, u32)", cells, 4)
+
+
+def main():
+    if len(sys.argv) != 2:
+        sys.exit("usage: {} <ucd directory>".format(sys.argv[0]))
+    ucd = sys.argv[1]
+
+    widths, version = read(ucd, "EastAsianWidth.txt")
+    categories, _ = read(ucd, os.path.join("extracted", "DerivedGeneralCategory.txt"))
+    breaks, _ = read(ucd, os.path.join("auxiliary", "GraphemeBreakProperty.txt"))
+    emoji, _ = read(ucd, os.path.join("emoji", "emoji-data.txt"))
+
+    # Marks drawn over their base, invisible format chars, and the Hangul
+    # vowels and final consonants a leading consonant is composed with
+    zero_width = chars(categories, {"Mn", "Me", "Cf"}) | chars(breaks, {"V", "T"})
+    wide = chars(widths, {"W", "F"}) - zero_width
+
+    unknown = {value for _, _, value in breaks} - set(GRAPHEME_BREAKS)
+    if unknown:
+        sys.exit("error: unknown Grapheme_Cluster_Break values {}".format(sorted(unknown)))
+    grapheme_breaks = merge(breaks)
+    pictographic = to_ranges(chars(emoji, {"Extended_Pictographic"}))
+
+    sections = [
+        "/*\n * UNICODE TABLES\n *\n"
+        " * Generated by scripts/unicode_tables.py from the Unicode {} character\n"
+        " * database, don't edit by hand.\n */".format(version),
+        ranges_table("ZERO_WIDTH", "Sorted, inclusive ranges of chars with no width of their own", to_ranges(zero_width)),
+        ranges_table("WIDE", "Sorted, inclusive ranges of chars two columns wide", to_ranges(wide)),
+        "// The Grapheme_Cluster_Break property, chars in none of the tables are Other\n"
+        "#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum GraphemeBreak {\n"
+        + "".join("    {},\n".format(variant) for variant in GRAPHEME_BREAKS.values())
+        + "    Other,\n}",
+        rust_table(
+            "GRAPHEME_BREAK",
+            "Sorted, inclusive ranges of chars and their Grapheme_Cluster_Break",
+            "(u32, u32, GraphemeBreak)",
+            ["(0x{:04X}, 0x{:04X}, GraphemeBreak::{}),".format(start, end, GRAPHEME_BREAKS[value])
+             for start, end, value in grapheme_breaks],
+            2,
+        ),
+        ranges_table("EXTENDED_PICTOGRAPHIC", "Sorted, inclusive ranges of Extended_Pictographic chars", pictographic),
+    ]
+
+    with open(OUTPUT, "w", encoding="utf-8") as f:
+        f.write("\n\n".join(sections) + "\n")
+    print("Wrote {} from Unicode {}".format(OUTPUT, version))
+
+
+if __name__ == "__main__":
+    main()